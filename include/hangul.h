@@ -0,0 +1,125 @@
+/*
+ * A C API of h_hangul, that follows libhangul's `hangul_ic_*` functions.
+ * Link with the cdylib of this crate (`libh_hangul.so`) instead of libhangul.
+ *
+ * The strings are null-terminated UCS-4, and they are owned by the context:
+ * they are valid until the next call with the same context.
+ */
+
+#ifndef H_HANGUL_H
+#define H_HANGUL_H
+
+/* Generated by cbindgen from src/capi.rs. Do not edit this file by hand. */
+
+#include <stdbool.h>
+#include <stdint.h>
+
+/*
+ An opaque handle for C
+ */
+typedef struct HangulInputContext HangulInputContext;
+
+/*
+ `ucschar` of libhangul
+ */
+typedef uint32_t ucschar;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Creates an input context. `keyboard` is an id of libhangul ("2", "39", "3f", "3s"),
+ or a name of `Layout::from_name`. It's Dubeolsik if `keyboard` is null.\
+ It returns null if the keyboard is unknown. The context has to be freed by `hangul_ic_delete`.
+
+ # Safety
+
+ `keyboard` has to be null, or a valid null-terminated string.
+ */
+HangulInputContext *hangul_ic_new(const char *keyboard);
+
+/*
+ # Safety
+
+ `hic` has to be null, or a context from `hangul_ic_new` that is not freed yet.
+ */
+void hangul_ic_delete(HangulInputContext *hic);
+
+/*
+ It returns `false` if `ascii` is not handled. In that case, the preedit is committed,
+ and the caller has to handle `ascii` after taking the commit string.
+
+ # Safety
+
+ `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+ */
+bool hangul_ic_process(HangulInputContext *hic, int ascii);
+
+/*
+ It returns `false` if the preedit is empty.
+
+ # Safety
+
+ `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+ */
+bool hangul_ic_backspace(HangulInputContext *hic);
+
+/*
+ The text that is being composed.
+
+ # Safety
+
+ `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+ */
+const ucschar *hangul_ic_get_preedit_string(HangulInputContext *hic);
+
+/*
+ The text that is committed by the last `hangul_ic_process`.
+
+ # Safety
+
+ `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+ */
+const ucschar *hangul_ic_get_commit_string(HangulInputContext *hic);
+
+/*
+ It commits the preedit, and returns it. The preedit and the commit string become empty.
+
+ # Safety
+
+ `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+ */
+const ucschar *hangul_ic_flush(HangulInputContext *hic);
+
+/*
+ It discards the preedit and the commit string.
+
+ # Safety
+
+ `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+ */
+void hangul_ic_reset(HangulInputContext *hic);
+
+/*
+ # Safety
+
+ `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+ */
+bool hangul_ic_is_empty(HangulInputContext *hic);
+
+/*
+ It changes the keyboard, after discarding the preedit. Unknown keyboards are ignored.
+
+ # Safety
+
+ `hic` has to be a context from `hangul_ic_new` that is not freed yet,
+ and `keyboard` has to be null, or a valid null-terminated string.
+ */
+void hangul_ic_select_keyboard(HangulInputContext *hic, const char *keyboard);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* H_HANGUL_H */
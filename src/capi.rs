@@ -0,0 +1,199 @@
+// A C API that follows libhangul's `hangul_ic_*` functions. The header is `include/hangul.h`,
+// which is generated by `cbindgen --config cbindgen.toml --output include/hangul.h`.
+// The strings are null-terminated UCS-4 (`ucschar*`), and they are owned by the context:
+// they are valid until the next call with the same context.
+
+use crate::input::InputContext;
+use crate::layout::Layout;
+use std::ffi::{CStr, c_char, c_int};
+use std::ptr;
+
+/// `ucschar` of libhangul
+pub type UcsChar = u32;
+
+/// An opaque handle for C
+pub struct HangulInputContext {
+    context: InputContext<'static>,
+    preedit: Vec<UcsChar>,
+    commit: Vec<UcsChar>,
+    flushed: Vec<UcsChar>,
+}
+
+impl HangulInputContext {
+    fn update_preedit(&mut self) {
+        self.preedit = to_ucs4(&self.context.preedit());
+    }
+}
+
+/// Creates an input context. `keyboard` is an id of libhangul ("2", "39", "3f", "3s"),
+/// or a name of `Layout::from_name`. It's Dubeolsik if `keyboard` is null.\
+/// It returns null if the keyboard is unknown. The context has to be freed by `hangul_ic_delete`.
+///
+/// # Safety
+///
+/// `keyboard` has to be null, or a valid null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn hangul_ic_new(keyboard: *const c_char) -> *mut HangulInputContext {
+    let layout = match layout_of(keyboard) {
+        Some(l) => l,
+        None => {
+            return ptr::null_mut();
+        }
+    };
+
+    Box::into_raw(Box::new(HangulInputContext {
+        context: InputContext::with_layout(layout),
+        preedit: vec![0],
+        commit: vec![0],
+        flushed: vec![0],
+    }))
+}
+
+/// # Safety
+///
+/// `hic` has to be null, or a context from `hangul_ic_new` that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hangul_ic_delete(hic: *mut HangulInputContext) {
+    if !hic.is_null() {
+        drop(Box::from_raw(hic));
+    }
+}
+
+/// It returns `false` if `ascii` is not handled. In that case, the preedit is committed,
+/// and the caller has to handle `ascii` after taking the commit string.
+///
+/// # Safety
+///
+/// `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hangul_ic_process(hic: *mut HangulInputContext, ascii: c_int) -> bool {
+    let hic = &mut *hic;
+
+    let (result, commit) = match u16::try_from(ascii) {
+        Ok(key) => (hic.context.process(key), hic.context.commit()),
+        _ => (false, hic.context.flush()),
+    };
+
+    hic.commit = to_ucs4(&commit);
+    hic.update_preedit();
+
+    result
+}
+
+/// It returns `false` if the preedit is empty.
+///
+/// # Safety
+///
+/// `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hangul_ic_backspace(hic: *mut HangulInputContext) -> bool {
+    let hic = &mut *hic;
+    let result = hic.context.backspace();
+
+    hic.commit = vec![0];
+    hic.update_preedit();
+
+    result
+}
+
+/// The text that is being composed.
+///
+/// # Safety
+///
+/// `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hangul_ic_get_preedit_string(hic: *mut HangulInputContext) -> *const UcsChar {
+    (*hic).preedit.as_ptr()
+}
+
+/// The text that is committed by the last `hangul_ic_process`.
+///
+/// # Safety
+///
+/// `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hangul_ic_get_commit_string(hic: *mut HangulInputContext) -> *const UcsChar {
+    (*hic).commit.as_ptr()
+}
+
+/// It commits the preedit, and returns it. The preedit and the commit string become empty.
+///
+/// # Safety
+///
+/// `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hangul_ic_flush(hic: *mut HangulInputContext) -> *const UcsChar {
+    let hic = &mut *hic;
+
+    hic.flushed = to_ucs4(&hic.context.flush());
+    hic.commit = vec![0];
+    hic.preedit = vec![0];
+
+    hic.flushed.as_ptr()
+}
+
+/// It discards the preedit and the commit string.
+///
+/// # Safety
+///
+/// `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hangul_ic_reset(hic: *mut HangulInputContext) {
+    let hic = &mut *hic;
+
+    hic.context.flush();
+    hic.commit = vec![0];
+    hic.preedit = vec![0];
+}
+
+/// # Safety
+///
+/// `hic` has to be a context from `hangul_ic_new` that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn hangul_ic_is_empty(hic: *mut HangulInputContext) -> bool {
+    (*hic).context.is_empty()
+}
+
+/// It changes the keyboard, after discarding the preedit. Unknown keyboards are ignored.
+///
+/// # Safety
+///
+/// `hic` has to be a context from `hangul_ic_new` that is not freed yet,
+/// and `keyboard` has to be null, or a valid null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn hangul_ic_select_keyboard(hic: *mut HangulInputContext, keyboard: *const c_char) {
+    let hic = &mut *hic;
+
+    if let Some(layout) = layout_of(keyboard) {
+        let moachigi = hic.context.moachigi();
+
+        hic.context = InputContext::with_layout(layout);
+        hic.context.set_moachigi(moachigi);
+        hic.commit = vec![0];
+        hic.preedit = vec![0];
+    }
+}
+
+// the ids of libhangul, and the names of `Layout::from_name`
+unsafe fn layout_of(keyboard: *const c_char) -> Option<Layout> {
+    if keyboard.is_null() {
+        return Some(Layout::dubeolsik());
+    }
+
+    let name = CStr::from_ptr(keyboard).to_str().ok()?;
+
+    match name {
+        "39" => Some(Layout::sebeolsik_390()),
+        "3f" => Some(Layout::sebeolsik_final()),
+        "3s" => Some(Layout::sebeolsik_noshift()),
+        _ => Layout::from_name(name),
+    }
+}
+
+// null-terminated
+fn to_ucs4(string: &[u16]) -> Vec<UcsChar> {
+    let mut result: Vec<UcsChar> = string.iter().map(|c| *c as UcsChar).collect();
+    result.push(0);
+
+    result
+}
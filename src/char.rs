@@ -1,10 +1,13 @@
 use crate::qwerty::*;
+use crate::layout::{Layout, keys_to_kor, kor_to_keys};
 use crate::constants::*;
+use crate::normalize::*;
 use crate::utils::*;
 use crate::error::KorError;
 
 mod traits;
 
+#[allow(clippy::derive_ord_xor_partial_ord)]
 #[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq)]
 pub struct KorChar {
     cho: Option<u16>,
@@ -39,8 +42,31 @@ impl KorChar {
         kor_to_qwerty(&[self.to_u16()])
     }
 
+    /// "kvfx", Sebeolsik 390 -> KorChar(곽)
+    pub fn from_qwerty_with_layout(qwerty: &[u16], layout: &Layout) -> Result<KorChar, KorError> {
+        let kor = keys_to_kor(qwerty, layout);
+
+        if kor.len() == 1 {
+            KorChar::from_u16(kor[0])
+        }
+
+        else {
+            Err(KorError::TooManyChars(kor))
+        }
+    }
+
+    /// KorChar(곽), Sebeolsik 390 -> "kvfx"
+    pub fn to_qwerty_with_layout(&self, layout: &Layout) -> Vec<u16> {
+        kor_to_keys(&[self.to_u16()], layout)
+    }
+
+    /// It also accepts the modern conjoining jamo (ᄀ, ᅡ, ᆨ, ...), which are read as their compatibility jamo.
     pub fn from_u16(c: u16) -> Result<KorChar, KorError> {
-        if c <= 'ㅣ' as u16 {
+        if is_conjoining(c) {
+            KorChar::from_u16(to_compatibility(c).unwrap())
+        }
+
+        else if c <= 'ㅣ' as u16 {
             if c >= 'ㄱ' as u16 {
                 if c < 'ㅏ' as u16 {
                     Ok(KorChar {
@@ -87,6 +113,32 @@ impl KorChar {
         }
     }
 
+    /// "ᄀ ᅪ ᆨ" -> KorChar(곽)\
+    /// "ᄀ" -> KorChar(ㄱ)
+    pub fn from_conjoining(conjoining: &[u16]) -> Result<KorChar, KorError> {
+        let composed = compose(conjoining);
+
+        if composed.len() == 1 {
+            KorChar::from_u16(composed[0])
+        }
+
+        else {
+            Err(KorError::TooManyChars(composed))
+        }
+    }
+
+    /// KorChar(곽) -> "ᄀ ᅪ ᆨ"\
+    /// KorChar(ㄱ) -> "ᄀ"
+    pub fn to_conjoining(&self) -> Vec<u16> {
+        match (self.cho, self.joong) {
+            (Some(_), Some(_)) => decompose(&[self.to_u16()]),
+            (Some(c), None) => vec![to_conjoining_cho(c).or_else(|| to_conjoining_jong(c)).unwrap_or(c)],
+            (None, Some(c)) => vec![to_conjoining_joong(c).unwrap_or(c)],
+            _ => vec![self.to_u16()],
+        }
+    }
+
+    #[allow(clippy::unnecessary_unwrap)]
     pub fn to_u16(&self) -> u16 {
         if self.joong.is_none() {
             if self.cho.is_some() {
@@ -148,6 +200,7 @@ impl KorChar {
         }
     }
 
+    #[allow(clippy::clone_on_copy)]
     pub fn set_cho(&self, c: u16) -> Result<Self, KorError> {
         if is_valid_cho(c) {
             let mut result = self.clone();
@@ -160,6 +213,7 @@ impl KorChar {
         }
     }
 
+    #[allow(clippy::clone_on_copy)]
     pub fn set_joong(&self, c: u16) -> Result<Self, KorError> {
         if is_valid_joong(c) {
             let mut result = self.clone();
@@ -172,6 +226,7 @@ impl KorChar {
         }
     }
 
+    #[allow(clippy::clone_on_copy, clippy::unnecessary_unwrap)]
     pub fn set_jong(&self, c: Option<u16>) -> Result<Self, KorError> {
         if c.is_none() || is_valid_jong(c.unwrap()) {
             let mut result = self.clone();
@@ -352,6 +407,7 @@ fn rev_ind_joong(c: u16) -> u16 {
 }
 
 // None -> 0, Some('ㄱ') -> 1, Some('ㄲ') -> 2, ...
+#[allow(clippy::unnecessary_unwrap)]
 fn rev_ind_jong(c: Option<u16>) -> u16 {
     #[cfg(test)]
     assert!(c.is_none() || is_valid_jong(c.unwrap()));
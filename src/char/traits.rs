@@ -7,6 +7,7 @@ impl fmt::Display for KorChar {
     }
 }
 
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl PartialOrd for KorChar {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         self.to_u16().partial_cmp(&other.to_u16())
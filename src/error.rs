@@ -10,5 +10,8 @@ pub enum KorError {
     TooManyChars(Vec<u16>),
     MissingCho,
     MissingJoong,
+
+    /// (line number, message)
+    InvalidLayout(usize, String),
     TODO,
 }
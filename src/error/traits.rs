@@ -3,6 +3,7 @@ use crate::utils::from_v16;
 use std::fmt;
 
 impl fmt::Display for KorError {
+    #[allow(clippy::useless_format)]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         write!(
             fmt, "{}",
@@ -14,6 +15,7 @@ impl fmt::Display for KorError {
                 KorError::InvalidJong(c) => format!("{} is not a valid jong-sung", try_convert(*c)),
                 KorError::InvalidHangul(c) => format!("{} is not a valid hangul", try_convert(*c)),
                 KorError::TooManyChars(s) => format!("expected one character, but got too many: {:?}", from_v16(s)),
+                KorError::InvalidLayout(line, message) => format!("line {line}: {message}"),
                 KorError::TODO => format!("There's an error, but I'm too lazy to impl a variant for that..."),
             },
         )
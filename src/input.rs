@@ -0,0 +1,627 @@
+use crate::char::{KorChar, disassemble};
+use crate::key_event::{CapsLock, KeyEvent};
+use crate::layout::{DUBEOLSIK, KeyValue, Layout, LayoutKind};
+use crate::utils::*;
+use std::borrow::Cow;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ParseState {
+    None,
+
+    // a vowel without a cho-sung, it can still grow (ml -> ㅢ)
+    Vowel(u16),
+    Cho(u16),
+    Joong(u16, u16),
+    Jong(u16, u16, u16),
+
+    // Sebeolsik: the keys tell the roles of the jamo, so it only has to remember the syllable
+    Syllable(Option<u16>, Option<u16>, Option<u16>),
+}
+
+/// The interface of the input contexts: `InputContext` for keyboards, and `KeypadContext` for phone keypads.
+pub trait HangulInput {
+    fn process(&mut self, key: u16) -> bool;
+    fn backspace(&mut self) -> bool;
+    fn preedit(&self) -> Vec<u16>;
+    fn commit(&mut self) -> Vec<u16>;
+    fn flush(&mut self) -> Vec<u16>;
+    fn is_empty(&self) -> bool;
+
+    /// It types `keys` and returns the whole text, including the preedit.
+    /// The keys that are not processed are typed as they are.
+    fn type_keys(&mut self, keys: &[u16]) -> Vec<u16> {
+        let mut result = Vec::with_capacity(keys.len());
+
+        for key in keys.iter() {
+            if self.process(*key) {
+                result.extend(self.commit());
+            }
+
+            else {
+                result.extend(self.flush());
+                result.push(*key);
+            }
+        }
+
+        result.extend(self.flush());
+
+        result
+    }
+}
+
+/// An input context, that composes Hangul key by key.\
+/// r, h, k, r -> preedit: 곽, backspace -> preedit: 과, flush -> 과
+#[derive(Clone, Debug)]
+pub struct InputContext<'a> {
+    layout: Cow<'a, Layout>,
+    state: ParseState,
+
+    // the text that is committed, but not taken by `commit` yet
+    committed: Vec<u16>,
+
+    moachigi: bool,
+    caps_lock: CapsLock,
+
+    // the last jamo of the preedit, only for moachigi
+    last_value: Option<KeyValue>,
+
+    // the preedit before each key, since the last commit
+    history: Vec<ParseState>,
+}
+
+impl Default for InputContext<'_> {
+    fn default() -> Self {
+        InputContext::new()
+    }
+}
+
+impl<'a> InputContext<'a> {
+    /// Dubeolsik
+    pub fn new() -> Self {
+        InputContext::with_layout_ref(&DUBEOLSIK)
+    }
+
+    pub fn with_layout(layout: Layout) -> Self {
+        InputContext::from_cow(Cow::Owned(layout))
+    }
+
+    /// `with_layout` without cloning the layout
+    pub fn with_layout_ref(layout: &'a Layout) -> Self {
+        InputContext::from_cow(Cow::Borrowed(layout))
+    }
+
+    fn from_cow(layout: Cow<'a, Layout>) -> Self {
+        InputContext {
+            layout,
+            state: ParseState::None,
+            committed: vec![],
+            moachigi: false,
+            caps_lock: CapsLock::default(),
+            last_value: None,
+            history: vec![],
+        }
+    }
+
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    /// 모아치기: the jamo of a syllable can be typed in any order (ㅏ, ㄱ, ㄴ -> 간).\
+    /// A jamo joins the preedit if its place (cho-sung, joong-sung or jong-sung) is empty,
+    /// or if it can be combined with the jamo that was typed right before it (ㄱ, ㄱ -> ㄲ).
+    /// Otherwise, it starts a new syllable. It only affects Sebeolsik layouts.
+    pub fn set_moachigi(&mut self, moachigi: bool) {
+        self.moachigi = moachigi;
+    }
+
+    pub fn moachigi(&self) -> bool {
+        self.moachigi
+    }
+
+    /// How Caps Lock of `KeyEvent`s works. The default is `CapsLock::Ignore`.
+    pub fn set_caps_lock(&mut self, caps_lock: CapsLock) {
+        self.caps_lock = caps_lock;
+    }
+
+    pub fn caps_lock(&self) -> CapsLock {
+        self.caps_lock
+    }
+
+    /// `process` with a key code, instead of a character. Backspace is handled by `backspace`.\
+    /// It returns `false` if the event is not consumed: the caller has to handle it (after taking
+    /// the committed text), for example, a backspace with an empty preedit or an Enter.
+    pub fn process_key_event(&mut self, event: KeyEvent) -> bool {
+        if event.is_backspace() {
+            return self.backspace();
+        }
+
+        match event.to_char(self.caps_lock) {
+            Some(c) => self.process(c),
+            None => {
+                self.commit_preedit();
+                false
+            }
+        }
+    }
+
+    /// It returns `false` if `key` is not in the layout. In that case, the preedit is committed
+    /// and `key` is not consumed: the caller has to handle it after taking the committed text.
+    pub fn process(&mut self, key: u16) -> bool {
+        let value = match self.layout.get(key) {
+            Some(v) => v,
+
+            // Dubeolsik also accepts the compatibility jamo (ㄱ, ㅏ, ...)
+            None if self.layout.kind == LayoutKind::Dubeolsik && (is_valid_consonant(key) || is_valid_vowel(key)) => KeyValue::Jamo(key),
+            None => {
+                self.commit_preedit();
+                return false;
+            }
+        };
+
+        self.process_value(value);
+        true
+    }
+
+    /// It undoes the last key of the preedit: 곽 -> 과 -> 고 -> ㄱ -> (empty), and ㄲ -> ㄱ if ㄲ is typed with ㄱ, ㄱ.
+    /// If a key has committed a text, the jamo of the preedit are removed one by one (각 + ㅏ -> 가가, backspace -> 가ㄱ).\
+    /// It returns `false` if the preedit is already empty.
+    pub fn backspace(&mut self) -> bool {
+        self.last_value = None;
+
+        if let Some(state) = self.history.pop() {
+            self.state = state;
+            return true;
+        }
+
+        if let ParseState::Syllable(cho, joong, jong) = self.state {
+            // ㄲ -> ㄱ, ㅘ -> ㅗ, ㄳ -> ㄱ
+            let shrink = |c: u16| disassemble(c).map(|(c1, _)| c1);
+
+            self.state = match (cho, joong, jong) {
+                (_, _, Some(j)) => ParseState::Syllable(cho, joong, shrink(j)),
+                (_, Some(j), None) => ParseState::Syllable(cho, shrink(j), None),
+                (Some(c), None, None) => ParseState::Syllable(shrink(c), None, None),
+                (None, None, None) => ParseState::None,
+            };
+
+            if self.state == ParseState::Syllable(None, None, None) {
+                self.state = ParseState::None;
+            }
+
+            return true;
+        }
+
+        let mut jamo = state_to_jamo(self.state);
+
+        if jamo.pop().is_none() {
+            return false;
+        }
+
+        let mut state = ParseState::None;
+
+        for c in jamo.into_iter() {
+            state = dubeolsik_step(state, c, &self.layout, &mut vec![]);
+        }
+
+        self.state = state;
+        true
+    }
+
+    /// The text that is being composed. It's usually a single character, but it can be more
+    /// when the jamo cannot form a syllable (ㄱ사 for `rtk`).
+    pub fn preedit(&self) -> Vec<u16> {
+        state_to_string(self.state)
+    }
+
+    /// It takes the text that is committed so far. The preedit is left as it is.
+    pub fn commit(&mut self) -> Vec<u16> {
+        std::mem::take(&mut self.committed)
+    }
+
+    /// It commits the preedit, and takes the whole committed text.
+    pub fn flush(&mut self) -> Vec<u16> {
+        self.commit_preedit();
+        self.commit()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state == ParseState::None && self.committed.is_empty()
+    }
+
+    fn commit_preedit(&mut self) {
+        self.committed.extend(state_to_string(self.state));
+        self.state = ParseState::None;
+        self.last_value = None;
+        self.history.clear();
+    }
+
+    fn process_value(&mut self, value: KeyValue) {
+        let c = match value {
+            KeyValue::Jamo(c) | KeyValue::Cho(c) | KeyValue::Joong(c) | KeyValue::Jong(c) => c,
+            KeyValue::Char(c) => {
+                self.commit_preedit();
+                self.committed.push(c);
+                return;
+            }
+        };
+
+        let state = self.state;
+        let committed = self.committed.len();
+
+        self.state = match self.layout.kind {
+            LayoutKind::Dubeolsik => dubeolsik_step(self.state, c, &self.layout, &mut self.committed),
+            LayoutKind::Sebeolsik => {
+                let value = match value {
+                    KeyValue::Jamo(c) if is_valid_vowel(c) => KeyValue::Joong(c),
+                    KeyValue::Jamo(c) => KeyValue::Cho(c),
+                    _ => value,
+                };
+
+                if self.moachigi {
+                    let combinable = matches!(
+                        (self.last_value, value),
+                        (Some(KeyValue::Cho(_)), KeyValue::Cho(_))
+                        | (Some(KeyValue::Joong(_)), KeyValue::Joong(_))
+                        | (Some(KeyValue::Jong(_)), KeyValue::Jong(_))
+                    );
+
+                    self.last_value = Some(value);
+                    moachigi_step(self.state, value, combinable, &self.layout, &mut self.committed)
+                }
+
+                else {
+                    sebeolsik_step(self.state, value, &self.layout, &mut self.committed)
+                }
+            }
+        };
+
+        if self.committed.len() == committed {
+            self.history.push(state);
+        }
+
+        else {
+            self.history.clear();
+        }
+    }
+}
+
+impl HangulInput for InputContext<'_> {
+    fn process(&mut self, key: u16) -> bool {
+        InputContext::process(self, key)
+    }
+
+    fn backspace(&mut self) -> bool {
+        InputContext::backspace(self)
+    }
+
+    fn preedit(&self) -> Vec<u16> {
+        InputContext::preedit(self)
+    }
+
+    fn commit(&mut self) -> Vec<u16> {
+        InputContext::commit(self)
+    }
+
+    fn flush(&mut self) -> Vec<u16> {
+        InputContext::flush(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        InputContext::is_empty(self)
+    }
+}
+
+// `c` is a valid consonant or vowel
+fn dubeolsik_step(state: ParseState, c: u16, layout: &Layout, result: &mut Vec<u16>) -> ParseState {
+    match state {
+        ParseState::None => {
+            if is_valid_consonant(c) {
+                ParseState::Cho(c)
+            }
+
+            else {
+                ParseState::Vowel(c)
+            }
+        }
+        ParseState::Vowel(v) => {
+            if is_valid_consonant(c) {
+                result.push(v);
+                ParseState::Cho(c)
+            }
+
+            // ml -> ㅢ
+            else {
+                match layout.combine(v, c) {
+                    Some(new_v) => ParseState::Vowel(new_v),
+                    _ => {
+                        result.push(v);
+                        ParseState::Vowel(c)
+                    }
+                }
+            }
+        }
+        ParseState::Cho(cho) => {
+            if is_valid_consonant(c) {
+                match layout.combine(cho, c) {
+                    // `ㄱㄱ` is `rr`, and `ㄲ` is `R` -> that means `rr` is not `ㄲ`
+                    Some(new_c) if !is_tensory(new_c) || layout.double_tap_tense => ParseState::Cho(new_c),
+                    _ => {
+                        result.push(cho);
+                        ParseState::Cho(c)
+                    }
+                }
+            }
+
+            else {
+                ParseState::Joong(cho, c)
+            }
+        }
+        ParseState::Joong(cho, j) => {
+            if is_valid_consonant(c) {
+                ParseState::Jong(cho, j, c)
+            }
+
+            else {
+                match layout.combine(j, c) {
+                    Some(new_v) => ParseState::Joong(cho, new_v),
+                    _ => {
+                        match KorChar::combine(Some(cho), Some(j), None) {
+                            Ok(h) => {
+                                result.push(h.to_u16());
+                            }
+                            _ => {
+                                result.push(cho);
+                                result.push(j);
+                            }
+                        }
+
+                        ParseState::Vowel(c)
+                    }
+                }
+            }
+        }
+        ParseState::Jong(cho, ju, jo) => {
+            if is_valid_consonant(c) {
+                match layout.combine(jo, c) {
+                    Some(new_c) if !is_tensory(new_c) || layout.double_tap_tense => ParseState::Jong(cho, ju, new_c),
+                    _ => {
+                        match KorChar::combine(Some(cho), Some(ju), Some(jo)) {
+                            Ok(h) => {
+                                result.push(h.to_u16());
+                            }
+                            _ => match KorChar::combine(Some(cho), Some(ju), None) {
+                                Ok(h) => {
+                                    result.push(h.to_u16());
+                                    result.push(jo);
+                                }
+                                _ => {
+                                    result.push(cho);
+                                    result.push(ju);
+                                    result.push(jo);
+                                }
+                            }
+                        }
+
+                        ParseState::Cho(c)
+                    }
+                }
+            }
+
+            else {
+                match disassemble(jo) {
+                    Some((c1, c2)) if !is_tensory(jo) => {
+
+                        match KorChar::combine(Some(cho), Some(ju), Some(c1)) {
+                            Ok(h) => {
+                                result.push(h.to_u16());
+                            }
+                            // (cho)    ju    c1    c2
+                            // ㄱ ㅅ    ㅣ    ㄱ     ㅅ
+                            _ => {
+                                let (c3, c4) = disassemble(cho).unwrap();
+                                let h = KorChar::combine(Some(c4), Some(ju), Some(c1)).unwrap();
+
+                                result.push(c3);
+                                result.push(h.to_u16());
+                            }
+                        }
+
+                        ParseState::Joong(c2, c)
+                    }
+                    _ => {
+                        match KorChar::combine(Some(cho), Some(ju), None) {
+                            Ok(h) => {
+                                result.push(h.to_u16());
+                            }
+                            // (cho)    ju    jo
+                            // ㄱ ㅅ    ㅣ    ㄷ
+                            _ => {
+                                let (c1, c2) = disassemble(cho).unwrap();
+                                let h = KorChar::combine(Some(c2), Some(ju), None).unwrap();
+
+                                result.push(c1);
+                                result.push(h.to_u16());
+                            }
+                        }
+
+                        ParseState::Joong(jo, c)
+                    }
+                }
+            }
+        }
+
+        // it's only for Sebeolsik
+        ParseState::Syllable(_, _, _) => unreachable!(),
+    }
+}
+
+// `value` is a cho-sung, a joong-sung or a jong-sung
+fn sebeolsik_step(state: ParseState, value: KeyValue, layout: &Layout, result: &mut Vec<u16>) -> ParseState {
+    let (cho, joong, jong) = match state {
+        ParseState::Syllable(cho, joong, jong) => (cho, joong, jong),
+        _ => (None, None, None),
+    };
+
+    let next_state = match value {
+        KeyValue::Cho(c) => match (cho, joong, jong) {
+            (None, None, None) => Some((Some(c), None, None)),
+
+            // ㄱ + ㄱ -> ㄲ
+            (Some(prev), None, None) => match layout.combine(prev, c) {
+                Some(new_c) if is_valid_cho(new_c) && layout.double_tap_tense => Some((Some(new_c), None, None)),
+                _ => None,
+            },
+            _ => None,
+        },
+        KeyValue::Joong(v) => match (joong, jong) {
+            (None, None) => Some((cho, Some(v), None)),
+            (Some(prev), None) => layout.combine(prev, v).map(|new_v| (cho, Some(new_v), None)),
+            _ => None,
+        },
+        KeyValue::Jong(j) => match (joong, jong) {
+            (Some(_), None) => Some((cho, joong, Some(j))),
+
+            // ㄱ + ㅅ -> ㄳ, ㄱ + ㄱ -> ㄲ
+            (Some(_), Some(prev)) => match layout.combine(prev, j) {
+                Some(new_j) if is_valid_jong(new_j) && (!is_tensory(new_j) || layout.double_tap_tense) => Some((cho, joong, Some(new_j))),
+                _ => None,
+            },
+            _ => None,
+        },
+        KeyValue::Jamo(_) | KeyValue::Char(_) => unreachable!(),
+    };
+
+    match next_state {
+        Some((cho, joong, jong)) => ParseState::Syllable(cho, joong, jong),
+        None => {
+            result.extend(state_to_string(state));
+
+            match value {
+                KeyValue::Cho(c) => ParseState::Syllable(Some(c), None, None),
+                KeyValue::Joong(v) => ParseState::Syllable(None, Some(v), None),
+                KeyValue::Jong(j) => ParseState::Syllable(None, None, Some(j)),
+                KeyValue::Jamo(_) | KeyValue::Char(_) => unreachable!(),
+            }
+        }
+    }
+}
+
+// `value` is a cho-sung, a joong-sung or a jong-sung, and it's typed in any order
+fn moachigi_step(state: ParseState, value: KeyValue, combinable: bool, layout: &Layout, result: &mut Vec<u16>) -> ParseState {
+    let (cho, joong, jong) = match state {
+        ParseState::Syllable(cho, joong, jong) => (cho, joong, jong),
+        _ => (None, None, None),
+    };
+
+    let next_state = match value {
+        KeyValue::Cho(c) => match cho {
+            None => Some((Some(c), joong, jong)),
+            Some(prev) if combinable => match layout.combine(prev, c) {
+                Some(new_c) if is_valid_cho(new_c) && layout.double_tap_tense => Some((Some(new_c), joong, jong)),
+                _ => None,
+            },
+            _ => None,
+        },
+        KeyValue::Joong(v) => match joong {
+            None => Some((cho, Some(v), jong)),
+            Some(prev) if combinable => layout.combine(prev, v).map(|new_v| (cho, Some(new_v), jong)),
+            _ => None,
+        },
+        KeyValue::Jong(j) => match jong {
+            None => Some((cho, joong, Some(j))),
+            Some(prev) if combinable => match layout.combine(prev, j) {
+                Some(new_j) if is_valid_jong(new_j) && (!is_tensory(new_j) || layout.double_tap_tense) => Some((cho, joong, Some(new_j))),
+                _ => None,
+            },
+            _ => None,
+        },
+        KeyValue::Jamo(_) | KeyValue::Char(_) => unreachable!(),
+    };
+
+    match next_state {
+        Some((cho, joong, jong)) => ParseState::Syllable(cho, joong, jong),
+
+        // a new syllable
+        None => {
+            result.extend(state_to_string(state));
+            sebeolsik_step(ParseState::None, value, layout, result)
+        }
+    }
+}
+
+// the text of a state, when it's committed
+fn state_to_string(state: ParseState) -> Vec<u16> {
+    match state {
+        ParseState::None => vec![],
+        ParseState::Vowel(v) => vec![v],
+        ParseState::Cho(c) => vec![c],
+        ParseState::Joong(c, j) => match KorChar::combine(Some(c), Some(j), None) {
+            Ok(h) => vec![h.to_u16()],
+            _ => {
+                let (c1, c2) = disassemble(c).unwrap();
+                let h = KorChar::combine(Some(c2), Some(j), None).unwrap();
+
+                vec![c1, h.to_u16()]
+            }
+        },
+        ParseState::Jong(c, ju, jo) => match KorChar::combine(Some(c), Some(ju), Some(jo)) {
+            Ok(h) => vec![h.to_u16()],
+            /*
+             *          ㅆ      ㄸ       ㄳ       ㄱ
+             *  ㅆ      쌌      땄      ㄱ샀      갔
+             *  ㄸ      싸ㄸ    따ㄸ     ㄱ사ㄸ    가ㄸ
+             *  ㄳ      싻      딳       ㄱ삯     갃
+             *  ㄱ      싹      딱       ㄱ삭     각
+             */
+            _ => if is_tensory(jo) {
+                match KorChar::combine(Some(c), Some(ju), None) {
+                    Ok(h) => vec![h.to_u16(), jo],
+                    _ => {
+                        let (c1, c2) = disassemble(c).unwrap();
+                        let h = KorChar::combine(Some(c2), Some(ju), None).unwrap();
+
+                        vec![c1, h.to_u16(), jo]
+                    }
+                }
+
+            } else {
+                let (c1, c2) = disassemble(c).unwrap();
+                let h = KorChar::combine(Some(c2), Some(ju), Some(jo)).unwrap();
+
+                vec![c1, h.to_u16()]
+            }
+        },
+        ParseState::Syllable(cho, joong, jong) => match (cho, joong) {
+            (Some(_), Some(_)) => vec![KorChar::combine(cho, joong, jong).unwrap().to_u16()],
+            _ => [cho, joong, jong].into_iter().flatten().collect(),
+        },
+    }
+}
+
+// the jamo that have to be typed to get to `state`
+fn state_to_jamo(state: ParseState) -> Vec<u16> {
+    let (c1, c2, c3) = match state {
+        ParseState::None => (None, None, None),
+        ParseState::Vowel(v) => (Some(v), None, None),
+        ParseState::Cho(c) => (Some(c), None, None),
+        ParseState::Joong(c, j) => (Some(c), Some(j), None),
+        ParseState::Jong(c, ju, jo) => (Some(c), Some(ju), Some(jo)),
+        ParseState::Syllable(cho, joong, jong) => (cho, joong, jong),
+    };
+
+    let mut result = vec![];
+
+    for c in [c1, c2, c3].into_iter().flatten() {
+        match disassemble(c) {
+            Some((c1, c2)) if !is_tensory(c) => {
+                result.push(c1);
+                result.push(c2);
+            }
+            _ => {
+                result.push(c);
+            }
+        }
+    }
+
+    result
+}
@@ -0,0 +1,163 @@
+use crate::char::KorChar;
+use crate::constants::*;
+use crate::normalize::compose;
+use crate::pronounce::pronounce_chars;
+use crate::utils::*;
+
+// lax, tense and aspirated stops are written with the same base letters
+const INITIALS: [&str; 19] = [
+    "k", "k", "n", "t", "t",
+    "ɾ", "m", "p", "p", "s",
+    "s", "", "t͡ɕ", "t͡ɕ", "t͡ɕʰ",
+    "kʰ", "tʰ", "pʰ", "h"
+];
+
+// ㄱ, ㄷ, ㅂ, ㅈ between voiced sounds
+const VOICED_INITIALS: [&str; 19] = [
+    "ɡ", "k", "n", "d", "t",
+    "ɾ", "m", "b", "p", "s",
+    "s", "", "d͡ʑ", "t͡ɕ", "t͡ɕʰ",
+    "kʰ", "tʰ", "pʰ", "h"
+];
+
+const VOWELS: [&str; 21] = [
+    "a", "ɛ", "ja", "jɛ", "ʌ",
+    "e", "jʌ", "je", "o", "wa",
+    "wɛ", "we", "jo", "u", "wʌ",
+    "we", "ɥi", "ju", "ɯ", "ɰi", "i"
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TenseMark {
+    /// ㄲ -> k͈
+    Diacritic,
+
+    /// ㄲ -> ˀk
+    Glottal,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IpaOptions {
+    pub tense_mark: TenseMark,
+}
+
+impl Default for IpaOptions {
+    fn default() -> Self {
+        IpaOptions {
+            tense_mark: TenseMark::Diacritic,
+        }
+    }
+}
+
+/// 한국어 -> [han.ɡu.ɡʌ]\
+/// The text goes through `pronounce` first, then each word is transcribed in brackets,
+/// with `.` between syllables. Characters that are not syllables are left as they are.
+///
+/// - ㄱ, ㄷ, ㅂ and ㅈ are voiced between voiced sounds: [ɡ], [d], [b], [d͡ʑ].
+/// - ㄹ is [ɾ] as a cho-sung, and [l] as a jong-sung or after a jong-sung ㄹ.
+/// - ㅅ and ㅆ are palatalized before ㅣ and y-glides: [ɕ].
+/// - The jong-sungs ㄱ, ㄷ and ㅂ are unreleased: [k̚], [t̚], [p̚].
+pub fn to_ipa(string: &[u16], options: &IpaOptions) -> Vec<u16> {
+    let string = compose(string);
+    let mut result = String::with_capacity(string.len() * 4);
+    let mut index = 0;
+
+    while index < string.len() {
+        if is_jamo(string[index]) {
+            let mut word = vec![];
+
+            while index < string.len() && is_jamo(string[index]) {
+                word.push(KorChar::from_u16(string[index]).unwrap());
+                index += 1;
+            }
+
+            result.push('[');
+            transcribe_word(&pronounce_chars(&word), options, &mut result);
+            result.push(']');
+        }
+
+        else {
+            result.push(char::from_u32(string[index] as u32).unwrap_or('?'));
+            index += 1;
+        }
+    }
+
+    into_v16(&result)
+}
+
+fn transcribe_word(word: &[KorChar], options: &IpaOptions, result: &mut String) {
+    let mut prev_jong: Option<Option<u16>> = None;
+
+    for c in word.iter() {
+        let (cho, joong, jong) = c.disassemble();
+        let (cho, joong) = (cho.unwrap(), joong.unwrap());
+
+        if prev_jong.is_some() {
+            result.push('.');
+        }
+
+        let voiced = match prev_jong {
+            // the beginning of a word
+            None => false,
+            Some(None) => true,
+            Some(Some(j)) => j == 'ㄴ' as u16 || j == 'ㄹ' as u16 || j == 'ㅁ' as u16 || j == 'ㅇ' as u16,
+        };
+
+        let initial = if cho == 'ㄹ' as u16 && prev_jong == Some(Some('ㄹ' as u16)) {
+            "l"
+        } else if (cho == 'ㅅ' as u16 || cho == 'ㅆ' as u16) && is_palatal(joong) {
+            "ɕ"
+        } else if voiced {
+            VOICED_INITIALS[REV_CHOS[cho as usize - 'ㄱ' as usize] as usize]
+        } else {
+            INITIALS[REV_CHOS[cho as usize - 'ㄱ' as usize] as usize]
+        };
+
+        if is_tensory(cho) {
+            match options.tense_mark {
+                // t͡ɕ͈: the diacritic goes after the whole affricate
+                TenseMark::Diacritic => {
+                    result.push_str(initial);
+                    result.push('\u{348}');
+                }
+                TenseMark::Glottal => {
+                    result.push('ˀ');
+                    result.push_str(initial);
+                }
+            }
+        }
+
+        else {
+            result.push_str(initial);
+        }
+
+        result.push_str(VOWELS[REV_JOONGS[joong as usize - 'ㅏ' as usize] as usize]);
+
+        if let Some(j) = jong {
+            result.push_str(match char::from_u32(j as u32).unwrap() {
+                'ㄱ' => "k̚",
+                'ㄴ' => "n",
+                'ㄷ' => "t̚",
+                'ㄹ' => "l",
+                'ㅁ' => "m",
+                'ㅂ' => "p̚",
+                'ㅇ' => "ŋ",
+                _ => unreachable!(),
+            });
+        }
+
+        prev_jong = Some(jong);
+    }
+}
+
+// ㅣ and the vowels that start with a y-glide
+fn is_palatal(joong: u16) -> bool {
+    joong == 'ㅣ' as u16 ||
+    joong == 'ㅑ' as u16 ||
+    joong == 'ㅒ' as u16 ||
+    joong == 'ㅕ' as u16 ||
+    joong == 'ㅖ' as u16 ||
+    joong == 'ㅛ' as u16 ||
+    joong == 'ㅠ' as u16 ||
+    joong == 'ㅟ' as u16
+}
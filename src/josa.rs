@@ -0,0 +1,290 @@
+use crate::char::KorChar;
+use crate::normalize::compose;
+use crate::utils::*;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Josa {
+    /// 은/는
+    EunNeun,
+
+    /// 이/가
+    IGa,
+
+    /// 을/를
+    EulReul,
+
+    /// 과/와
+    GwaWa,
+
+    /// 아/야
+    AYa,
+
+    /// 이어/여
+    IeoYeo,
+
+    /// 으로/로\
+    /// 로 also follows the jong-sung ㄹ (서울로).
+    EuroRo,
+}
+
+impl Josa {
+    /// Josa::EulReul -> ("을", "를")\
+    /// (after a jong-sung, otherwise)
+    pub fn forms(&self) -> (&'static str, &'static str) {
+        match self {
+            Josa::EunNeun => ("은", "는"),
+            Josa::IGa => ("이", "가"),
+            Josa::EulReul => ("을", "를"),
+            Josa::GwaWa => ("과", "와"),
+            Josa::AYa => ("아", "야"),
+            Josa::IeoYeo => ("이어", "여"),
+            Josa::EuroRo => ("으로", "로"),
+        }
+    }
+
+    // "을(를)", "(으)로"
+    fn both_forms(&self) -> String {
+        match self {
+            Josa::EuroRo => "(으)로".to_string(),
+            _ => format!("{}({})", self.forms().0, self.forms().1),
+        }
+    }
+}
+
+// how a word ends when it's read aloud
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Final {
+    Vowel,
+    Rieul,
+    Consonant,
+    Unknown,
+}
+
+/// "사과", Josa::EulReul -> "를"\
+/// "3", Josa::IGa -> "이" (삼)\
+/// "Apple", Josa::EulReul -> "을" (애플)
+///
+/// It chooses the particle by how the end of `word` is read.
+///
+/// - Hangul: the jong-sung of the last syllable. A single jamo is read as its name (ㄱ -> 기역).
+/// - Numbers: the Sino-Korean reading. Trailing zeros are read as a unit (10 -> 십, 1000 -> 천, 10000 -> 만).
+/// - Latin letters: a word in capitals, or a single letter, is read letter by letter (SQL -> 에스큐엘).
+///   Other words are guessed from their English spelling (Apple -> 애플, Excel -> 엑셀, Computer -> 컴퓨터).
+/// - Parentheses, brackets and quotes at the end are skipped (사과(apple) -> 사과).
+///
+/// If it cannot tell, both forms are returned ("을(를)", "(으)로").
+pub fn josa(word: &[u16], josa: Josa) -> Vec<u16> {
+    let (with_jong, without_jong) = josa.forms();
+
+    let particle = match (final_sound(&compose(word)), josa) {
+        (Final::Vowel, _) | (Final::Rieul, Josa::EuroRo) => without_jong.to_string(),
+        (Final::Rieul, _) | (Final::Consonant, _) => with_jong.to_string(),
+        (Final::Unknown, _) => josa.both_forms(),
+    };
+
+    into_v16(&particle)
+}
+
+/// "사과", Josa::EulReul -> "사과를"
+pub fn attach_josa(word: &[u16], josa: Josa) -> Vec<u16> {
+    let mut result = word.to_vec();
+    result.extend(self::josa(word, josa));
+
+    result
+}
+
+/// format!("{}", with_josa("서울", Josa::EuroRo)) -> "서울로"
+pub fn with_josa(word: &str, josa: Josa) -> WithJosa<'_> {
+    WithJosa { word, josa }
+}
+
+/// A `Display` adaptor that writes the word, and then the particle.
+#[derive(Clone, Copy, Debug)]
+pub struct WithJosa<'a> {
+    word: &'a str,
+    josa: Josa,
+}
+
+impl fmt::Display for WithJosa<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}{}", self.word, from_v16(&josa(&into_v16(self.word), self.josa)))
+    }
+}
+
+fn final_sound(word: &[u16]) -> Final {
+    let word = strip_trailing_marks(word);
+
+    let last = match word.last() {
+        Some(l) => *l,
+        None => {
+            return Final::Unknown;
+        }
+    };
+
+    if is_jamo(last) {
+        hangul_final(KorChar::from_u16(last).unwrap().disassemble().2)
+    }
+
+    // ㄱ -> 기역, ㅏ -> 아
+    else if is_hangul(last) {
+        let c = KorChar::from_u16(last).unwrap().to_u16();
+
+        if is_valid_vowel(c) {
+            Final::Vowel
+        }
+
+        else if c == 'ㄹ' as u16 {
+            Final::Rieul
+        }
+
+        else {
+            Final::Consonant
+        }
+    }
+
+    else if is_digit(last) {
+        number_final(word)
+    }
+
+    else if is_latin(last) {
+        let start = word.iter().rposition(|c| !is_latin(*c)).map(|i| i + 1).unwrap_or(0);
+        let latin: String = from_v16(&word[start..]);
+
+        if latin.len() == 1 || latin.chars().all(|c| c.is_ascii_uppercase()) {
+            letter_final(latin.chars().last().unwrap())
+        }
+
+        else {
+            english_final(&latin.to_ascii_lowercase())
+        }
+    }
+
+    else {
+        Final::Unknown
+    }
+}
+
+// 사과(apple) -> 사과, "사과" -> 사과
+fn strip_trailing_marks(word: &[u16]) -> &[u16] {
+    let mut end = word.len();
+
+    while end > 0 {
+        let last = word[end - 1];
+
+        if last == ')' as u16 || last == ']' as u16 {
+            let open = if last == ')' as u16 { '(' as u16 } else { '[' as u16 };
+
+            match word[..end - 1].iter().rposition(|c| *c == open) {
+                Some(i) => {
+                    end = i;
+                }
+                None => {
+                    break;
+                }
+            }
+        }
+
+        else if last == '"' as u16 || last == '\'' as u16 || last == ' ' as u16
+            || last == '”' as u16 || last == '’' as u16 || last == '」' as u16 || last == '』' as u16 {
+            end -= 1;
+        }
+
+        else {
+            break;
+        }
+    }
+
+    &word[..end]
+}
+
+fn hangul_final(jong: Option<u16>) -> Final {
+    match jong {
+        Some(j) if j == 'ㄹ' as u16 => Final::Rieul,
+        Some(_) => Final::Consonant,
+        None => Final::Vowel,
+    }
+}
+
+// 영 일 이 삼 사 오 육 칠 팔 구
+const DIGITS: [Final; 10] = [
+    Final::Consonant, Final::Rieul, Final::Vowel, Final::Consonant, Final::Vowel,
+    Final::Vowel, Final::Consonant, Final::Rieul, Final::Rieul, Final::Vowel,
+];
+
+// 만 억 조 경
+const LARGE_UNITS: [Final; 4] = [Final::Consonant, Final::Consonant, Final::Vowel, Final::Consonant];
+
+fn number_final(word: &[u16]) -> Final {
+    let start = word.iter().rposition(|c| !is_digit(*c)).map(|i| i + 1).unwrap_or(0);
+    let digits = &word[start..];
+
+    // 3.10 -> 삼 점 일 영
+    let is_decimal = start >= 2 && word[start - 1] == '.' as u16 && is_digit(word[start - 2]);
+    let zeros = digits.iter().rev().take_while(|c| **c == '0' as u16).count();
+
+    if is_decimal || zeros == 0 || zeros == digits.len() {
+        return DIGITS[(digits[digits.len() - 1] - '0' as u16) as usize];
+    }
+
+    // 십 백 천
+    if zeros < 4 {
+        return Final::Consonant;
+    }
+
+    // 10^13 -> 십조, the last unit is the large one
+    match LARGE_UNITS.get(zeros / 4 - 1) {
+        Some(f) => *f,
+        None => Final::Unknown,
+    }
+}
+
+// 에이 비 씨 디 이 에프 지 에이치 아이 제이 케이 엘 엠 엔 오 피 큐 알 에스 티 유 브이 더블유 엑스 와이 지
+fn letter_final(c: char) -> Final {
+    match c.to_ascii_uppercase() {
+        'L' | 'R' => Final::Rieul,
+        'M' | 'N' => Final::Consonant,
+        _ => Final::Vowel,
+    }
+}
+
+// It follows how English words are usually written in Hangul (외래어 표기법).
+fn english_final(word: &str) -> Final {
+    let bytes = word.as_bytes();
+    let is_vowel = |c: u8| b"aeiou".contains(&c);
+    let len = bytes.len();
+    let last = bytes[len - 1];
+
+    // apple -> 애플, table -> 테이블, email -> 이메일
+    let is_rieul = last == b'l' || (word.ends_with("le") && len >= 3 && !is_vowel(bytes[len - 3]));
+
+    // team -> 팀, phone -> 폰, king -> 킹
+    let is_nasal = last == b'm' || last == b'n' || word.ends_with("ng") || word.ends_with("ne") || word.ends_with("me");
+
+    // click -> 클릭, book -> 북, chat -> 챗, hip -> 힙, but boat -> 보트
+    let is_stop = word.ends_with("ck") || word.ends_with("ook")
+        || ((last == b'p' || last == b't' || last == b'k')
+            && len >= 2 && is_vowel(bytes[len - 2])
+            && (len == 2 || !is_vowel(bytes[len - 3])));
+
+    if is_rieul {
+        Final::Rieul
+    }
+
+    else if is_nasal || is_stop {
+        Final::Consonant
+    }
+
+    // computer -> 컴퓨터, mouse -> 마우스, dog -> 도그
+    else {
+        Final::Vowel
+    }
+}
+
+fn is_digit(c: u16) -> bool {
+    c >= '0' as u16 && c <= '9' as u16
+}
+
+fn is_latin(c: u16) -> bool {
+    c < 128 && (c as u8).is_ascii_alphabetic()
+}
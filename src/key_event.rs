@@ -0,0 +1,57 @@
+// evdev key codes (linux/input-event-codes.h)
+const KEY_BACKSPACE: u16 = 14;
+
+// (code, the character of the key on US QWERTY, with Shift)
+const KEY_CODES: [(u16, char, char); 48] = [
+    (41, '`', '~'), (2, '1', '!'), (3, '2', '@'), (4, '3', '#'), (5, '4', '$'),
+    (6, '5', '%'), (7, '6', '^'), (8, '7', '&'), (9, '8', '*'), (10, '9', '('),
+    (11, '0', ')'), (12, '-', '_'), (13, '=', '+'),
+    (16, 'q', 'Q'), (17, 'w', 'W'), (18, 'e', 'E'), (19, 'r', 'R'), (20, 't', 'T'),
+    (21, 'y', 'Y'), (22, 'u', 'U'), (23, 'i', 'I'), (24, 'o', 'O'), (25, 'p', 'P'),
+    (26, '[', '{'), (27, ']', '}'), (43, '\\', '|'),
+    (30, 'a', 'A'), (31, 's', 'S'), (32, 'd', 'D'), (33, 'f', 'F'), (34, 'g', 'G'),
+    (35, 'h', 'H'), (36, 'j', 'J'), (37, 'k', 'K'), (38, 'l', 'L'), (39, ';', ':'),
+    (40, '\'', '"'),
+    (44, 'z', 'Z'), (45, 'x', 'X'), (46, 'c', 'C'), (47, 'v', 'V'), (48, 'b', 'B'),
+    (49, 'n', 'N'), (50, 'm', 'M'), (51, ',', '<'), (52, '.', '>'), (53, '/', '?'),
+    (57, ' ', ' '),
+];
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CapsLock {
+    /// Caps Lock does nothing in Hangul mode: `r` is ㄱ, and Shift + `r` is ㄲ.
+    #[default]
+    Ignore,
+
+    /// Caps Lock inverts Shift on the letter keys, as it does in Latin mode: `r` is ㄲ, and Shift + `r` is ㄱ.
+    Invert,
+}
+
+/// A key press, with an evdev key code (`KEY_Q` is 16) and the modifiers.\
+/// The code is the position of the key, so the layout of the OS does not matter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct KeyEvent {
+    pub code: u16,
+    pub shift: bool,
+    pub caps_lock: bool,
+}
+
+impl KeyEvent {
+    pub fn new(code: u16, shift: bool, caps_lock: bool) -> Self {
+        KeyEvent { code, shift, caps_lock }
+    }
+
+    /// KeyEvent::new(19, true, false) -> 'R'\
+    /// The character of the key on US QWERTY, which is what the layouts are written in.
+    /// It returns `None` for the keys that do not type characters (Enter, arrows, ...).
+    pub fn to_char(&self, caps_lock: CapsLock) -> Option<u16> {
+        let (_, c, shifted) = KEY_CODES.iter().find(|(code, _, _)| *code == self.code)?;
+        let shift = if c.is_ascii_alphabetic() && self.caps_lock && caps_lock == CapsLock::Invert { !self.shift } else { self.shift };
+
+        Some(if shift { *shifted as u16 } else { *c as u16 })
+    }
+
+    pub fn is_backspace(&self) -> bool {
+        self.code == KEY_BACKSPACE
+    }
+}
@@ -0,0 +1,520 @@
+use crate::char::KorChar;
+use crate::input::{HangulInput, InputContext};
+use crate::layout::Layout;
+use crate::normalize::{compose, to_compatibility};
+use crate::utils::*;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref CHEONJIIN_COMBINATIONS: Layout = Layout::parse(include_str!("layouts/keypad_cheonjiin.txt")).unwrap();
+    static ref MULTITAP_COMBINATIONS: Layout = Layout::parse(include_str!("layouts/keypad_multitap.txt")).unwrap();
+}
+
+/// ㆍ (U+318D), typed with the `2` key
+pub const ARAEA: u16 = 0x318D;
+
+/// ᆢ (U+11A2), typed with `2` twice
+pub const SSANG_ARAEA: u16 = 0x11A2;
+
+// (key, the jamo in the order of presses)
+const CHEONJIIN_KEYS: [(u16, &[u16]); 7] = [
+    ('4' as u16, &['ㄱ' as u16, 'ㅋ' as u16, 'ㄲ' as u16]),
+    ('5' as u16, &['ㄴ' as u16, 'ㄹ' as u16]),
+    ('6' as u16, &['ㄷ' as u16, 'ㅌ' as u16, 'ㄸ' as u16]),
+    ('7' as u16, &['ㅂ' as u16, 'ㅍ' as u16, 'ㅃ' as u16]),
+    ('8' as u16, &['ㅅ' as u16, 'ㅎ' as u16, 'ㅆ' as u16]),
+    ('9' as u16, &['ㅈ' as u16, 'ㅊ' as u16, 'ㅉ' as u16]),
+    ('0' as u16, &['ㅇ' as u16, 'ㅁ' as u16]),
+];
+
+// (key, stroke)
+const STROKE_KEYS: [(u16, u16); 3] = [
+    ('1' as u16, 'ㅣ' as u16),
+    ('2' as u16, ARAEA),
+    ('3' as u16, 'ㅡ' as u16),
+];
+
+// (vowel, stroke, the new vowel)
+const VOWEL_STROKES: [(u16, u16, u16); 20] = [
+    ('ㅣ' as u16, ARAEA, 'ㅏ' as u16),
+    ('ㅏ' as u16, ARAEA, 'ㅑ' as u16),
+    ('ㅏ' as u16, 'ㅣ' as u16, 'ㅐ' as u16),
+    ('ㅑ' as u16, 'ㅣ' as u16, 'ㅒ' as u16),
+    (ARAEA, ARAEA, SSANG_ARAEA),
+    (ARAEA, 'ㅣ' as u16, 'ㅓ' as u16),
+    (SSANG_ARAEA, 'ㅣ' as u16, 'ㅕ' as u16),
+    ('ㅓ' as u16, 'ㅣ' as u16, 'ㅔ' as u16),
+    ('ㅕ' as u16, 'ㅣ' as u16, 'ㅖ' as u16),
+    (ARAEA, 'ㅡ' as u16, 'ㅗ' as u16),
+    (SSANG_ARAEA, 'ㅡ' as u16, 'ㅛ' as u16),
+    ('ㅗ' as u16, 'ㅣ' as u16, 'ㅚ' as u16),
+    ('ㅚ' as u16, ARAEA, 'ㅘ' as u16),
+    ('ㅘ' as u16, 'ㅣ' as u16, 'ㅙ' as u16),
+    ('ㅡ' as u16, ARAEA, 'ㅜ' as u16),
+    ('ㅜ' as u16, ARAEA, 'ㅠ' as u16),
+    ('ㅜ' as u16, 'ㅣ' as u16, 'ㅟ' as u16),
+    ('ㅠ' as u16, 'ㅣ' as u16, 'ㅝ' as u16),
+    ('ㅝ' as u16, 'ㅣ' as u16, 'ㅞ' as u16),
+    ('ㅡ' as u16, 'ㅣ' as u16, 'ㅢ' as u16),
+];
+
+const NARATGEUL_KEYS: [(u16, &[u16]); 10] = [
+    ('1' as u16, &['ㄱ' as u16]),
+    ('2' as u16, &['ㄴ' as u16]),
+    ('3' as u16, &['ㅏ' as u16, 'ㅓ' as u16]),
+    ('4' as u16, &['ㄹ' as u16]),
+    ('5' as u16, &['ㅁ' as u16]),
+    ('6' as u16, &['ㅗ' as u16, 'ㅜ' as u16]),
+    ('7' as u16, &['ㅅ' as u16]),
+    ('8' as u16, &['ㅇ' as u16]),
+    ('9' as u16, &['ㅣ' as u16]),
+    ('0' as u16, &['ㅡ' as u16]),
+];
+
+const SKY_KEYS: [(u16, &[u16]); 12] = [
+    ('1' as u16, &['ㄱ' as u16, 'ㅋ' as u16, 'ㄲ' as u16]),
+    ('2' as u16, &['ㅣ' as u16, 'ㅡ' as u16, 'ㅢ' as u16]),
+    ('3' as u16, &['ㅏ' as u16, 'ㅑ' as u16]),
+    ('4' as u16, &['ㄷ' as u16, 'ㅌ' as u16, 'ㄸ' as u16]),
+    ('5' as u16, &['ㄴ' as u16, 'ㄹ' as u16]),
+    ('6' as u16, &['ㅓ' as u16, 'ㅕ' as u16]),
+    ('7' as u16, &['ㅁ' as u16, 'ㅅ' as u16, 'ㅆ' as u16]),
+    ('8' as u16, &['ㅂ' as u16, 'ㅍ' as u16, 'ㅃ' as u16]),
+    ('9' as u16, &['ㅗ' as u16, 'ㅛ' as u16]),
+    ('*' as u16, &['ㅈ' as u16, 'ㅊ' as u16, 'ㅉ' as u16]),
+    ('0' as u16, &['ㅇ' as u16, 'ㅎ' as u16]),
+    ('#' as u16, &['ㅜ' as u16, 'ㅠ' as u16]),
+];
+
+// 나랏글 획추가 (`*`): ㄴ -> ㄷ -> ㅌ -> ㄴ
+const NARATGEUL_STROKES: [&[u16]; 9] = [
+    &['ㄱ' as u16, 'ㅋ' as u16],
+    &['ㄴ' as u16, 'ㄷ' as u16, 'ㅌ' as u16],
+    &['ㅁ' as u16, 'ㅂ' as u16, 'ㅍ' as u16],
+    &['ㅅ' as u16, 'ㅈ' as u16, 'ㅊ' as u16],
+    &['ㅇ' as u16, 'ㅎ' as u16],
+    &['ㅏ' as u16, 'ㅑ' as u16],
+    &['ㅓ' as u16, 'ㅕ' as u16],
+    &['ㅗ' as u16, 'ㅛ' as u16],
+    &['ㅜ' as u16, 'ㅠ' as u16],
+];
+
+// 나랏글 쌍자음 (`#`): ㄱ -> ㄲ -> ㄱ
+const NARATGEUL_DOUBLES: [&[u16]; 5] = [
+    &['ㄱ' as u16, 'ㄲ' as u16],
+    &['ㄷ' as u16, 'ㄸ' as u16],
+    &['ㅂ' as u16, 'ㅃ' as u16],
+    &['ㅅ' as u16, 'ㅆ' as u16],
+    &['ㅈ' as u16, 'ㅉ' as u16],
+];
+
+const NARATGEUL_STROKE_KEY: u16 = '*' as u16;
+const NARATGEUL_DOUBLE_KEY: u16 = '#' as u16;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeypadLayout {
+    /// 천지인 (Samsung)\
+    /// 1: ㅣ, 2: ㆍ, 3: ㅡ, 4: ㄱㅋㄲ, 5: ㄴㄹ, 6: ㄷㅌㄸ, 7: ㅂㅍㅃ, 8: ㅅㅎㅆ, 9: ㅈㅊㅉ, 0: ㅇㅁ, *: separator, #: space
+    ///
+    /// The vowels are built from strokes (ㅣ, ㆍ -> ㅏ), and the consonants cycle by repeated presses (ㄱ -> ㅋ -> ㄲ).
+    Cheonjiin,
+
+    /// 나랏글 (LG)\
+    /// 1: ㄱ, 2: ㄴ, 3: ㅏㅓ, 4: ㄹ, 5: ㅁ, 6: ㅗㅜ, 7: ㅅ, 8: ㅇ, 9: ㅣ, 0: ㅡ, *: 획추가, #: 쌍자음, -: separator
+    ///
+    /// `*` adds a stroke to the last jamo (ㄴ -> ㄷ -> ㅌ, ㅏ -> ㅑ), and `#` doubles it (ㄱ -> ㄲ).
+    Naratgeul,
+
+    /// 스카이 (Pantech), every key cycles by repeated presses\
+    /// 1: ㄱㅋㄲ, 2: ㅣㅡㅢ, 3: ㅏㅑ, 4: ㄷㅌㄸ, 5: ㄴㄹ, 6: ㅓㅕ, 7: ㅁㅅㅆ, 8: ㅂㅍㅃ, 9: ㅗㅛ, *: ㅈㅊㅉ, 0: ㅇㅎ, #: ㅜㅠ, -: separator
+    Sky,
+}
+
+impl KeypadLayout {
+    /// "cheonjiin", "naratgeul" or "sky"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "cheonjiin" => Some(KeypadLayout::Cheonjiin),
+            "naratgeul" => Some(KeypadLayout::Naratgeul),
+            "sky" => Some(KeypadLayout::Sky),
+            _ => None,
+        }
+    }
+
+    /// It stands for a timeout: the next key starts a new jamo (`4*4` -> ㄱㄱ, while `44` -> ㅋ in Cheonjiin).\
+    /// Naratgeul and Sky do not have a spare key for it, so `-` is used.
+    pub fn separator(&self) -> u16 {
+        match self {
+            KeypadLayout::Cheonjiin => '*' as u16,
+            KeypadLayout::Naratgeul | KeypadLayout::Sky => '-' as u16,
+        }
+    }
+
+    /// The key that types a space. Naratgeul and Sky use the space itself.
+    pub fn space(&self) -> Option<u16> {
+        match self {
+            KeypadLayout::Cheonjiin => Some('#' as u16),
+            KeypadLayout::Naratgeul | KeypadLayout::Sky => None,
+        }
+    }
+
+    // the keys that type jamo, by repeated presses
+    fn keys(&self) -> &'static [(u16, &'static [u16])] {
+        match self {
+            KeypadLayout::Cheonjiin => &CHEONJIIN_KEYS,
+            KeypadLayout::Naratgeul => &NARATGEUL_KEYS,
+            KeypadLayout::Sky => &SKY_KEYS,
+        }
+    }
+
+    // the jamo are composed with this layout
+    fn combinations(&self) -> &'static Layout {
+        match self {
+            KeypadLayout::Cheonjiin => &CHEONJIIN_COMBINATIONS,
+            KeypadLayout::Naratgeul | KeypadLayout::Sky => &MULTITAP_COMBINATIONS,
+        }
+    }
+
+    fn jamo_of(&self, key: u16) -> Option<&'static [u16]> {
+        self.keys().iter().find(|(k, _)| *k == key).map(|(_, j)| *j)
+    }
+
+    // the keys that type `c` (ㅋ -> "44" in Cheonjiin, "1*" in Naratgeul)
+    fn keys_of(&self, c: u16) -> Option<Vec<u16>> {
+        if let Some(keys) = self.keys().iter().find_map(|(k, jamo)| jamo.iter().position(|j| *j == c).map(|i| vec![*k; i + 1])) {
+            return Some(keys);
+        }
+
+        match self {
+            KeypadLayout::Cheonjiin if is_valid_vowel(c) || c == ARAEA || c == SSANG_ARAEA => {
+                Some(strokes_of(c).iter().map(|s| STROKE_KEYS.iter().find(|(_, s_)| s_ == s).unwrap().0).collect())
+            }
+            KeypadLayout::Naratgeul => {
+                for (modifier, chains) in [(NARATGEUL_STROKE_KEY, &NARATGEUL_STROKES[..]), (NARATGEUL_DOUBLE_KEY, &NARATGEUL_DOUBLES[..])] {
+                    for chain in chains.iter() {
+                        if let Some(index @ 1..) = chain.iter().position(|j| *j == c) {
+                            let mut result = self.keys_of(chain[0])?;
+                            result.extend(vec![modifier; index]);
+
+                            return Some(result);
+                        }
+                    }
+                }
+
+                None
+            }
+            _ => None,
+        }
+    }
+}
+
+/// An input context for 12-key phone keypads.\
+/// The keypads move the jong-sungs as they go (각 + ㅣ -> 가기), so the preedit is the whole word
+/// that is being typed, not a single syllable.
+#[derive(Clone, Debug)]
+pub struct KeypadContext {
+    layout: KeypadLayout,
+
+    // the jamo of the preedit, that may include ㆍ and ᆢ
+    jamo: Vec<u16>,
+
+    // the text that is committed, but not taken by `commit` yet
+    committed: Vec<u16>,
+
+    last_key: Option<u16>,
+}
+
+impl Default for KeypadContext {
+    fn default() -> Self {
+        KeypadContext::new()
+    }
+}
+
+impl KeypadContext {
+    /// Cheonjiin
+    pub fn new() -> Self {
+        KeypadContext::with_layout(KeypadLayout::Cheonjiin)
+    }
+
+    pub fn with_layout(layout: KeypadLayout) -> Self {
+        KeypadContext {
+            layout,
+            jamo: vec![],
+            committed: vec![],
+            last_key: None,
+        }
+    }
+
+    pub fn layout(&self) -> KeypadLayout {
+        self.layout
+    }
+
+    /// It returns `false` if `key` is not on the keypad. In that case, the preedit is committed
+    /// and `key` is not consumed: the caller has to handle it after taking the committed text.
+    pub fn process(&mut self, key: u16) -> bool {
+        if Some(key) == self.layout.space() {
+            self.commit_preedit();
+            self.committed.push(' ' as u16);
+        }
+
+        // `last_key` is enough to start a new jamo
+        else if key == self.layout.separator() {}
+
+        else if let (KeypadLayout::Cheonjiin, Some(stroke)) = (self.layout, stroke_of(key)) {
+            let combined = match self.jamo.last() {
+                Some(v) if self.last_key != Some(self.layout.separator()) => add_stroke(*v, stroke),
+                _ => None,
+            };
+
+            match combined {
+                Some(v) => {
+                    *self.jamo.last_mut().unwrap() = v;
+                }
+                None => {
+                    self.jamo.push(stroke);
+                }
+            }
+        }
+
+        else if self.layout == KeypadLayout::Naratgeul && (key == NARATGEUL_STROKE_KEY || key == NARATGEUL_DOUBLE_KEY) {
+            let chains = if key == NARATGEUL_STROKE_KEY { &NARATGEUL_STROKES[..] } else { &NARATGEUL_DOUBLES[..] };
+
+            // it does nothing if the last jamo cannot take the stroke
+            if let Some(c) = self.jamo.last_mut() {
+                if let Some(next) = next_in(chains, *c) {
+                    *c = next;
+                }
+            }
+        }
+
+        else if let Some(jamo) = self.layout.jamo_of(key) {
+            match self.jamo.last() {
+                Some(c) if self.last_key == Some(key) && jamo.len() > 1 && jamo.contains(c) => {
+                    let index = jamo.iter().position(|c_| c_ == c).unwrap();
+                    *self.jamo.last_mut().unwrap() = jamo[(index + 1) % jamo.len()];
+                }
+                _ => {
+                    self.jamo.push(jamo[0]);
+                }
+            }
+        }
+
+        else {
+            self.commit_preedit();
+            return false;
+        }
+
+        self.last_key = Some(key);
+        true
+    }
+
+    /// It undoes the last jamo. With Cheonjiin, it undoes the last stroke of a vowel (ㅑ -> ㅏ -> ㅣ).\
+    /// It returns `false` if the preedit is already empty.
+    pub fn backspace(&mut self) -> bool {
+        self.last_key = None;
+
+        let last = match self.jamo.pop() {
+            Some(c) => c,
+            None => {
+                return false;
+            }
+        };
+
+        if self.layout == KeypadLayout::Cheonjiin {
+            if let Some((prev, _, _)) = VOWEL_STROKES.iter().find(|(_, _, v)| *v == last) {
+                self.jamo.push(*prev);
+            }
+        }
+
+        true
+    }
+
+    /// The word that is being composed. An unfinished vowel of Cheonjiin is shown as ㆍ or ᆢ.
+    pub fn preedit(&self) -> Vec<u16> {
+        compose_jamo(&self.jamo, self.layout)
+    }
+
+    /// It takes the text that is committed so far. The preedit is left as it is.
+    pub fn commit(&mut self) -> Vec<u16> {
+        std::mem::take(&mut self.committed)
+    }
+
+    /// It commits the preedit, and takes the whole committed text.
+    pub fn flush(&mut self) -> Vec<u16> {
+        self.commit_preedit();
+        self.commit()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jamo.is_empty() && self.committed.is_empty()
+    }
+
+    fn commit_preedit(&mut self) {
+        self.committed.extend(compose_jamo(&self.jamo, self.layout));
+        self.jamo.clear();
+        self.last_key = None;
+    }
+}
+
+impl HangulInput for KeypadContext {
+    fn process(&mut self, key: u16) -> bool {
+        KeypadContext::process(self, key)
+    }
+
+    fn backspace(&mut self) -> bool {
+        KeypadContext::backspace(self)
+    }
+
+    fn preedit(&self) -> Vec<u16> {
+        KeypadContext::preedit(self)
+    }
+
+    fn commit(&mut self) -> Vec<u16> {
+        KeypadContext::commit(self)
+    }
+
+    fn flush(&mut self) -> Vec<u16> {
+        KeypadContext::flush(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        KeypadContext::is_empty(self)
+    }
+}
+
+/// "81255120" -> 사랑, "4124*412" -> 각가
+pub fn cheonjiin_to_kor(string: &[u16]) -> Vec<u16> {
+    keypad_to_kor(string, KeypadLayout::Cheonjiin)
+}
+
+/// 각가 -> "4124*412"
+pub fn kor_to_cheonjiin(string: &[u16]) -> Vec<u16> {
+    kor_to_keypad(string, KeypadLayout::Cheonjiin)
+}
+
+/// "1*32337" (Naratgeul) -> 카넛\
+/// The keys that are not on the keypad are typed as they are.
+pub fn keypad_to_kor(string: &[u16], layout: KeypadLayout) -> Vec<u16> {
+    KeypadContext::with_layout(layout).type_keys(string)
+}
+
+/// 카넛 -> "1*32337" (Naratgeul)\
+/// A separator is inserted where two jamo would be merged otherwise. The characters that are
+/// also keys (digits, `*`, `#`, ...) are left as they are, so they cannot be converted back.
+pub fn kor_to_keypad(string: &[u16], layout: KeypadLayout) -> Vec<u16> {
+    let string = compose(string);
+    let mut result = Vec::with_capacity(string.len() * 6);
+
+    // the last key of the word
+    let mut prev = None;
+
+    for c in string.iter() {
+        let c = to_compatibility(*c).unwrap_or(*c);
+
+        let jamo = if is_jamo(c) {
+            let (cho, joong, jong) = KorChar::from_u16(c).unwrap().disassemble();
+            [cho, joong, jong].into_iter().flatten().collect()
+        }
+
+        else if is_non_jamo(c) || c == ARAEA || c == SSANG_ARAEA {
+            vec![c]
+        }
+
+        else {
+            result.push(if c == ' ' as u16 { layout.space().unwrap_or(c) } else { c });
+            prev = None;
+            continue;
+        };
+
+        for c_ in jamo.into_iter() {
+            push_keys(c_, layout, &mut prev, &mut result);
+        }
+    }
+
+    result
+}
+
+// ㄳ is typed as ㄱ and ㅅ, and ㅐ as ㅏ and ㅣ, if the layout doesn't have a key for it
+fn push_keys(c: u16, layout: KeypadLayout, prev: &mut Option<u16>, result: &mut Vec<u16>) {
+    let keys = match layout.keys_of(c) {
+        Some(keys) => keys,
+        None => {
+            match layout.combinations().split(c) {
+                Some((c1, c2)) => {
+                    push_keys(c1, layout, prev, result);
+                    push_keys(c2, layout, prev, result);
+                }
+                None => {
+                    result.push(c);
+                    *prev = None;
+                }
+            }
+
+            return;
+        }
+    };
+
+    let first = keys[0];
+
+    // the same key would cycle the last jamo, and a stroke would be added to the last vowel
+    let needs_separator = match *prev {
+        Some(p) if p == first => layout.jamo_of(first).map(|jamo| jamo.len() > 1).unwrap_or(true),
+        Some(p) => layout == KeypadLayout::Cheonjiin && stroke_of(p).is_some() && stroke_of(first).is_some(),
+        None => false,
+    };
+
+    if needs_separator {
+        result.push(layout.separator());
+    }
+
+    *prev = keys.last().copied();
+    result.extend(keys);
+}
+
+// the dots (ㆍ, ᆢ) are not jamo, they are left as they are
+fn compose_jamo(jamo: &[u16], layout: KeypadLayout) -> Vec<u16> {
+    let mut result = Vec::with_capacity(jamo.len());
+    let mut context = InputContext::with_layout_ref(layout.combinations());
+
+    for c in jamo.iter() {
+        if *c == ARAEA || *c == SSANG_ARAEA {
+            result.extend(context.flush());
+            result.push(*c);
+        }
+
+        else {
+            context.process(*c);
+            result.extend(context.commit());
+        }
+    }
+
+    result.extend(context.flush());
+
+    result
+}
+
+// ㄴ -> ㄷ, ㅌ -> ㄴ
+fn next_in(chains: &[&[u16]], c: u16) -> Option<u16> {
+    chains.iter().find_map(|chain| chain.iter().position(|c_| *c_ == c).map(|i| chain[(i + 1) % chain.len()]))
+}
+
+fn stroke_of(key: u16) -> Option<u16> {
+    STROKE_KEYS.iter().find(|(k, _)| *k == key).map(|(_, s)| *s)
+}
+
+fn add_stroke(vowel: u16, stroke: u16) -> Option<u16> {
+    VOWEL_STROKES.iter().find(|(v, s, _)| *v == vowel && *s == stroke).map(|(_, _, v)| *v)
+}
+
+// ㅘ -> ㆍ, ㅡ, ㅣ, ㆍ
+fn strokes_of(vowel: u16) -> Vec<u16> {
+    match VOWEL_STROKES.iter().find(|(_, _, v)| *v == vowel) {
+        Some((prev, stroke, _)) => {
+            let mut result = strokes_of(*prev);
+            result.push(*stroke);
+
+            result
+        }
+        None => vec![vowel],
+    }
+}
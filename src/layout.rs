@@ -0,0 +1,650 @@
+use crate::char::{KorChar, assemble};
+use crate::error::KorError;
+use crate::input::{HangulInput, InputContext};
+use crate::normalize::{compose, to_compatibility};
+use crate::utils::*;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::ops::Range;
+
+lazy_static! {
+    pub(crate) static ref DUBEOLSIK: Layout = Layout::dubeolsik();
+
+    // every pair that `assemble` accepts
+    static ref DEFAULT_COMBINATIONS: HashMap<(u16, u16), u16> = {
+        let mut result = HashMap::new();
+
+        for c1 in 'ㄱ' as u16..='ㅣ' as u16 {
+            for c2 in 'ㄱ' as u16..='ㅣ' as u16 {
+                if let Some(c) = assemble(c1, c2) {
+                    result.insert((c1, c2), c);
+                }
+            }
+        }
+
+        result
+    };
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum KeyValue {
+    /// A jamo whose role is decided by the automaton (Dubeolsik)
+    Jamo(u16),
+    Cho(u16),
+    Joong(u16),
+    Jong(u16),
+
+    /// A character that is not a part of a syllable
+    Char(u16),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LayoutKind {
+    /// The consonant keys are shared by cho-sungs and jong-sungs.
+    Dubeolsik,
+
+    /// Cho-sungs, joong-sungs and jong-sungs have their own keys.
+    Sebeolsik,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layout {
+    pub name: String,
+    pub kind: LayoutKind,
+
+    /// If it's set, typing a consonant twice makes its tense form (ㄱㄱ -> ㄲ).
+    /// Otherwise, the tense consonants are typed with their own keys.
+    pub double_tap_tense: bool,
+
+    keys: HashMap<u16, KeyValue>,
+
+    // the key of each value, unshifted keys are preferred
+    rev_keys: HashMap<KeyValue, u16>,
+
+    // (ㅗ, ㅏ) -> ㅘ
+    combinations: HashMap<(u16, u16), u16>,
+
+    // ㅘ -> (ㅗ, ㅏ), the reverse of `combinations`
+    splits: HashMap<u16, (u16, u16)>,
+}
+
+impl Layout {
+    /// The keys that are not in `keys` are typed as they are.
+    pub fn new(name: &str, kind: LayoutKind, keys: &[(u16, KeyValue)]) -> Self {
+        let mut result = Layout {
+            name: name.to_string(),
+            kind,
+            double_tap_tense: kind == LayoutKind::Sebeolsik,
+            keys: HashMap::with_capacity(keys.len()),
+            rev_keys: HashMap::with_capacity(keys.len()),
+            combinations: HashMap::new(),
+            splits: HashMap::new(),
+        };
+
+        result.set_combinations(DEFAULT_COMBINATIONS.clone());
+
+        for (key, value) in keys.iter() {
+            result.keys.insert(*key, *value);
+
+            match result.rev_keys.get(value) {
+                Some(k) if !is_shifted(*k) || is_shifted(*key) => {}
+                _ => {
+                    result.rev_keys.insert(*value, *key);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Reads a layout definition. See the files in `src/layouts/` for examples.
+    ///
+    /// ```text
+    /// // a comment
+    /// name sebeolsik-390
+    /// kind sebeolsik
+    /// double-tap-tense yes
+    /// combine ㅗ ㅏ ㅘ
+    ///
+    /// q jong ㅅ | jong ㅍ
+    /// , - | char 2
+    /// ```
+    ///
+    /// - `kind` is `dubeolsik` or `sebeolsik`, and it's the only required header.
+    /// - `double-tap-tense` is `yes` or `no`. The default is `yes` for Sebeolsik, and `no` for Dubeolsik.
+    /// - `combine a b c` lines replace the default jamo combinations (ㄱㅅ -> ㄳ, ㅗㅏ -> ㅘ, ...).
+    /// - A key line has a key, the value of the key, and optionally ` | ` and the value of the key with Shift (US layout).
+    ///   A value is `jamo`, `cho`, `joong`, `jong` or `char` followed by a character, or `-` for nothing.
+    pub fn parse(definition: &str) -> Result<Self, KorError> {
+        let mut name = String::from("custom");
+        let mut kind = None;
+        let mut double_tap_tense = None;
+        let mut combinations = HashMap::new();
+        let mut keys = vec![];
+
+        for (index, line) in definition.lines().enumerate() {
+            let line_no = index + 1;
+            let error = |message: &str| KorError::InvalidLayout(line_no, message.to_string());
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+
+            let (first, rest) = match line.split_once(char::is_whitespace) {
+                Some((first, rest)) => (first, rest.trim()),
+                None => {
+                    return Err(error("a value is missing"));
+                }
+            };
+
+            match first {
+                "name" => {
+                    name = rest.to_string();
+                }
+                "kind" => {
+                    kind = match rest {
+                        "dubeolsik" => Some(LayoutKind::Dubeolsik),
+                        "sebeolsik" => Some(LayoutKind::Sebeolsik),
+                        _ => {
+                            return Err(error(&format!("unknown kind: {rest}")));
+                        }
+                    };
+                }
+                "double-tap-tense" => {
+                    double_tap_tense = match rest {
+                        "yes" => Some(true),
+                        "no" => Some(false),
+                        _ => {
+                            return Err(error(&format!("expected `yes` or `no`, but got {rest}")));
+                        }
+                    };
+                }
+                "combine" => {
+                    let jamo: Vec<u16> = rest.split_whitespace().map(parse_char).collect::<Option<Vec<u16>>>().unwrap_or_default();
+
+                    if jamo.len() != 3 || !jamo.iter().all(|c| is_non_jamo(*c)) {
+                        return Err(error("expected three jamo: `combine ㅗ ㅏ ㅘ`"));
+                    }
+
+                    // the automaton assumes that a combination does not change the role of the jamo
+                    if !jamo.iter().all(|c| is_valid_vowel(*c)) && !jamo.iter().all(|c| is_valid_consonant(*c)) {
+                        return Err(error("a combination has to be three vowels or three consonants: `combine ㄱ ㅅ ㄳ`"));
+                    }
+
+                    combinations.insert((jamo[0], jamo[1]), jamo[2]);
+                }
+                _ => {
+                    let key = match parse_char(first) {
+                        Some(k) => k,
+                        None => {
+                            return Err(error(&format!("unknown header: {first}")));
+                        }
+                    };
+
+                    let (value, shifted_value) = match rest.split_once(" | ") {
+                        Some((value, shifted_value)) => (value, Some(shifted_value)),
+                        None => (rest, None),
+                    };
+
+                    if let Some(v) = parse_value(value).map_err(|e| error(&e))? {
+                        keys.push((key, v));
+                    }
+
+                    if let Some(shifted_value) = shifted_value {
+                        let shifted_key = match shift(key) {
+                            Some(k) => k,
+                            None => {
+                                return Err(error(&format!("{first} does not have a shifted key")));
+                            }
+                        };
+
+                        if let Some(v) = parse_value(shifted_value).map_err(|e| error(&e))? {
+                            keys.push((shifted_key, v));
+                        }
+                    }
+                }
+            }
+        }
+
+        let kind = match kind {
+            Some(k) => k,
+            None => {
+                return Err(KorError::InvalidLayout(definition.lines().count(), "`kind` is missing".to_string()));
+            }
+        };
+
+        let mut result = Layout::new(&name, kind, &keys);
+
+        if let Some(d) = double_tap_tense {
+            result.double_tap_tense = d;
+        }
+
+        if !combinations.is_empty() {
+            result.set_combinations(combinations);
+        }
+
+        Ok(result)
+    }
+
+    /// 두벌식 표준
+    pub fn dubeolsik() -> Self {
+        Layout::parse(include_str!("layouts/dubeolsik.txt")).unwrap()
+    }
+
+    /// 세벌식 390
+    pub fn sebeolsik_390() -> Self {
+        Layout::parse(include_str!("layouts/sebeolsik_390.txt")).unwrap()
+    }
+
+    /// 세벌식 최종
+    pub fn sebeolsik_final() -> Self {
+        Layout::parse(include_str!("layouts/sebeolsik_final.txt")).unwrap()
+    }
+
+    /// 세벌식 순아래\
+    /// Sebeolsik 390, with the jong-sungs that need Shift also on the unshifted punctuation keys.
+    pub fn sebeolsik_noshift() -> Self {
+        Layout::parse(include_str!("layouts/sebeolsik_noshift.txt")).unwrap()
+    }
+
+    /// "2" or "dubeolsik", "390", "final", "noshift" (or with a "sebeolsik-" prefix)
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+
+        match name.strip_prefix("sebeolsik-").unwrap_or(&name) {
+            "2" | "dubeolsik" => Some(Layout::dubeolsik()),
+            "390" => Some(Layout::sebeolsik_390()),
+            "final" => Some(Layout::sebeolsik_final()),
+            "noshift" => Some(Layout::sebeolsik_noshift()),
+            _ => None,
+        }
+    }
+
+    pub fn get(&self, key: u16) -> Option<KeyValue> {
+        self.keys.get(&key).copied()
+    }
+
+    /// The key that types `value`. If more than one key types it, an unshifted one is preferred.
+    pub fn key_of(&self, value: KeyValue) -> Option<u16> {
+        self.rev_keys.get(&value).copied()
+    }
+
+    /// ㅗ, ㅏ -> ㅘ\
+    /// ㄱ, ㄱ -> ㄲ (whether it's typed that way depends on `double_tap_tense`)
+    pub fn combine(&self, c1: u16, c2: u16) -> Option<u16> {
+        self.combinations.get(&(c1, c2)).copied()
+    }
+
+    /// ㅘ -> ㅗ, ㅏ\
+    /// the reverse of `combine`
+    pub fn split(&self, c: u16) -> Option<(u16, u16)> {
+        self.splits.get(&c).copied()
+    }
+
+    fn set_combinations(&mut self, combinations: HashMap<(u16, u16), u16>) {
+        self.splits.clear();
+
+        // if more than one pair makes the same jamo, the smallest one is used
+        for (pair, c) in combinations.iter() {
+            match self.splits.get(c) {
+                Some(p) if p <= pair => {}
+                _ => {
+                    self.splits.insert(*c, *pair);
+                }
+            }
+        }
+
+        self.combinations = combinations;
+    }
+}
+
+/// "kfkd" (Sebeolsik 390) -> 낭\
+/// `qwerty_to_kor` with another layout
+pub fn keys_to_kor(string: &[u16], layout: &Layout) -> Vec<u16> {
+    InputContext::with_layout_ref(layout).type_keys(string)
+}
+
+/// 낭 -> "kfa" (Sebeolsik 390)\
+/// `kor_to_qwerty` with another layout. Jamo that do not have their own keys are typed in halves (ㄲ -> ㄱㄱ, ㅘ -> ㅗㅏ).
+pub fn kor_to_keys(string: &[u16], layout: &Layout) -> Vec<u16> {
+    let string = compose(string);
+    let mut result = Vec::with_capacity(string.len() * 3);
+
+    for c in string.iter() {
+        let c = to_compatibility(*c).unwrap_or(*c);
+
+        if is_jamo(c) {
+            let (cho, joong, jong) = KorChar::from_u16(c).unwrap().disassemble();
+
+            match layout.kind {
+                LayoutKind::Dubeolsik => {
+                    for c_ in [cho, joong, jong].into_iter().flatten() {
+                        push_keys(KeyValue::Jamo(c_), layout, &mut result);
+                    }
+                }
+                LayoutKind::Sebeolsik => {
+                    push_keys(KeyValue::Cho(cho.unwrap()), layout, &mut result);
+                    push_keys(KeyValue::Joong(joong.unwrap()), layout, &mut result);
+
+                    if let Some(j) = jong {
+                        push_keys(KeyValue::Jong(j), layout, &mut result);
+                    }
+                }
+            }
+        }
+
+        else if is_non_jamo(c) {
+            let value = match layout.kind {
+                LayoutKind::Dubeolsik => KeyValue::Jamo(c),
+                LayoutKind::Sebeolsik if is_valid_vowel(c) => KeyValue::Joong(c),
+                LayoutKind::Sebeolsik if is_valid_cho(c) => KeyValue::Cho(c),
+                LayoutKind::Sebeolsik => KeyValue::Jong(c),
+            };
+
+            push_keys(value, layout, &mut result);
+        }
+
+        // the key of `c` may type something else
+        else if layout.get(c).is_some() {
+            result.push(layout.key_of(KeyValue::Char(c)).unwrap_or(c));
+        }
+
+        else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// "rkrk" -> (가가, [0..2, 2..4])\
+/// `keys_to_kor`, with the range of the keys that typed each character of the result.
+/// A key can be in two ranges, when its jamo is split (`ㄳ` + `ㅏ` -> ㄱ사).
+pub fn keys_to_kor_with_offsets(string: &[u16], layout: &Layout) -> (Vec<u16>, Vec<Range<usize>>) {
+    let result = keys_to_kor(string, layout);
+    let mut offsets = Vec::with_capacity(result.len());
+
+    // `bounds[i]` is the index of the first jamo that `string[i]` types, when the jamo are split into atoms (ㄲ -> ㄱㄱ)
+    let mut bounds = Vec::with_capacity(string.len() + 1);
+    bounds.push(0);
+
+    for key in string.iter() {
+        bounds.push(bounds[bounds.len() - 1] + key_atoms(*key, layout));
+    }
+
+    let mut atom = 0;
+
+    for c in result.iter() {
+        let atoms = char_atoms(*c, layout);
+        let start = (bounds.partition_point(|b| *b <= atom) - 1).min(string.len().saturating_sub(1));
+        let end = bounds.partition_point(|b| *b < atom + atoms).clamp(start + 1, string.len());
+
+        offsets.push(start..end);
+        atom += atoms;
+    }
+
+    (result, offsets)
+}
+
+/// 가가 -> ("rkrk", [0..1, 0..1, 1..2, 1..2])\
+/// `kor_to_keys`, with the range of the characters that typed each key. A range is longer than 1
+/// when conjoining jamo (NFD) are composed.
+pub fn kor_to_keys_with_offsets(string: &[u16], layout: &Layout) -> (Vec<u16>, Vec<Range<usize>>) {
+    let mut result = Vec::with_capacity(string.len() * 3);
+    let mut offsets = Vec::with_capacity(string.len() * 3);
+    let mut index = 0;
+
+    while index < string.len() {
+        // the longest run that composes to a single character
+        let mut end = index + 1;
+
+        while end < string.len() && compose(&string[index..end + 1]).len() == 1 {
+            end += 1;
+        }
+
+        let keys = kor_to_keys(&string[index..end], layout);
+        offsets.extend(vec![index..end; keys.len()]);
+        result.extend(keys);
+        index = end;
+    }
+
+    (result, offsets)
+}
+
+/// The result of `keys_to_kor_lossless`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Lossless {
+    pub text: Vec<u16>,
+
+    /// (a range of `text`, the keys that typed it), for the parts that `kor_to_keys` does not restore.
+    /// They are sorted, and do not overlap.
+    pub patches: Vec<(Range<usize>, Vec<u16>)>,
+}
+
+/// "dKssud" -> Lossless { text: 안녕, patches: [(0..1, "dKs")] }\
+/// `keys_to_kor`, with the keys that `kor_to_keys` cannot restore: Shift on the keys that do not need it,
+/// Caps Lock, and the ambiguous sequences (`rt` and `ㄳ`). `lossless_to_keys` gives the exact `string` back.
+pub fn keys_to_kor_lossless(string: &[u16], layout: &Layout) -> Lossless {
+    let (text, offsets) = keys_to_kor_with_offsets(string, layout);
+
+    // (a range of `text`, a range of `string`), the ranges of the keys do not overlap
+    let mut segments: Vec<(Range<usize>, Range<usize>)> = vec![];
+    let mut index = 0;
+    let mut key_end = 0;
+
+    while index < text.len() {
+        let start = index;
+        let key_start = key_end;
+
+        while index < text.len() && (index == start || offsets[index].start < key_end) {
+            key_end = key_end.max(offsets[index].end);
+            index += 1;
+        }
+
+        segments.push((start..index, key_start..key_end));
+    }
+
+    // the keys that type nothing
+    if key_end < string.len() {
+        match segments.last_mut() {
+            Some((_, keys)) => {
+                keys.end = string.len();
+            }
+            None => {
+                segments.push((0..0, 0..string.len()));
+            }
+        }
+    }
+
+    let mut patches = vec![];
+    let mut restored: Option<(Range<usize>, Range<usize>)> = None;
+
+    for (chars, keys) in segments.into_iter() {
+        if kor_to_keys(&text[chars.clone()], layout) == string[keys.clone()] {
+            restored = match restored {
+                Some((c, k)) => Some((c.start..chars.end, k.start..keys.end)),
+                None => Some((chars, keys)),
+            };
+        }
+
+        else {
+            // `lossless_to_keys` converts the whole run at once, so the run is checked again
+            if let Some((c, k)) = restored.take() {
+                if kor_to_keys(&text[c.clone()], layout) != string[k.clone()] {
+                    push_patch(&mut patches, c, &string[k]);
+                }
+            }
+
+            push_patch(&mut patches, chars, &string[keys]);
+        }
+    }
+
+    if let Some((c, k)) = restored {
+        if kor_to_keys(&text[c.clone()], layout) != string[k.clone()] {
+            push_patch(&mut patches, c, &string[k]);
+        }
+    }
+
+    Lossless { text, patches }
+}
+
+/// the reverse of `keys_to_kor_lossless`
+pub fn lossless_to_keys(lossless: &Lossless, layout: &Layout) -> Vec<u16> {
+    let mut result = Vec::with_capacity(lossless.text.len() * 3);
+    let mut index = 0;
+
+    for (chars, keys) in lossless.patches.iter() {
+        result.extend(kor_to_keys(&lossless.text[index..chars.start], layout));
+        result.extend_from_slice(keys);
+        index = chars.end;
+    }
+
+    result.extend(kor_to_keys(&lossless.text[index..], layout));
+
+    result
+}
+
+// merges adjacent patches
+fn push_patch(patches: &mut Vec<(Range<usize>, Vec<u16>)>, chars: Range<usize>, keys: &[u16]) {
+    match patches.last_mut() {
+        Some((last, last_keys)) if last.end == chars.start => {
+            last.end = chars.end;
+            last_keys.extend_from_slice(keys);
+        }
+        _ => {
+            patches.push((chars, keys.to_vec()));
+        }
+    }
+}
+
+// the number of jamo that a key types (ㅘ -> 2, ㄲ -> 2, a -> 1)
+fn key_atoms(key: u16, layout: &Layout) -> usize {
+    match layout.get(key) {
+        Some(KeyValue::Jamo(c) | KeyValue::Cho(c) | KeyValue::Joong(c) | KeyValue::Jong(c)) => jamo_atoms(c, layout),
+        Some(KeyValue::Char(_)) => 1,
+        None if is_non_jamo(key) => jamo_atoms(key, layout),
+        None => 1,
+    }
+}
+
+fn char_atoms(c: u16, layout: &Layout) -> usize {
+    if is_jamo(c) {
+        let (cho, joong, jong) = KorChar::from_u16(c).unwrap().disassemble();
+
+        [cho, joong, jong].into_iter().flatten().map(|j| jamo_atoms(j, layout)).sum()
+    }
+
+    else if is_non_jamo(c) {
+        jamo_atoms(c, layout)
+    }
+
+    else {
+        1
+    }
+}
+
+fn jamo_atoms(c: u16, layout: &Layout) -> usize {
+    match layout.split(c) {
+        Some((c1, c2)) => jamo_atoms(c1, layout) + jamo_atoms(c2, layout),
+        None => 1,
+    }
+}
+
+fn push_keys(value: KeyValue, layout: &Layout, result: &mut Vec<u16>) {
+    if let Some(k) = layout.key_of(value) {
+        result.push(k);
+        return;
+    }
+
+    let (c, tag): (u16, fn(u16) -> KeyValue) = match value {
+        KeyValue::Jamo(c) => (c, KeyValue::Jamo),
+        KeyValue::Cho(c) => (c, KeyValue::Cho),
+        KeyValue::Joong(c) => (c, KeyValue::Joong),
+        KeyValue::Jong(c) => (c, KeyValue::Jong),
+        KeyValue::Char(c) => (c, KeyValue::Char),
+    };
+
+    match layout.split(c) {
+        Some((c1, c2)) => {
+            push_keys(tag(c1), layout, result);
+            push_keys(tag(c2), layout, result);
+        }
+
+        // it cannot be typed with this layout
+        _ => {
+            result.push(c);
+        }
+    }
+}
+
+fn is_shifted(key: u16) -> bool {
+    key < 128 && ((key as u8).is_ascii_uppercase() || b"~!@#$%^&*()_+{}|:\"<>?".contains(&(key as u8)))
+}
+
+// the key that is typed with Shift on the US layout
+fn shift(key: u16) -> Option<u16> {
+    const UNSHIFTED: &[u8] = b"`1234567890-=[]\\;',./";
+    const SHIFTED: &[u8] = b"~!@#$%^&*()_+{}|:\"<>?";
+
+    if key < 128 && (key as u8).is_ascii_lowercase() {
+        Some((key as u8).to_ascii_uppercase() as u16)
+    }
+
+    else {
+        UNSHIFTED.iter().position(|k| *k as u16 == key).map(|i| SHIFTED[i] as u16)
+    }
+}
+
+// `None` if `s` is not exactly one character
+fn parse_char(s: &str) -> Option<u16> {
+    let v16 = into_v16(s);
+
+    if v16.len() == 1 {
+        Some(v16[0])
+    }
+
+    else {
+        None
+    }
+}
+
+// "jong ㅅ" -> Some(KeyValue::Jong(ㅅ)), "-" -> None
+fn parse_value(s: &str) -> Result<Option<KeyValue>, String> {
+    let s = s.trim();
+
+    if s == "-" {
+        return Ok(None);
+    }
+
+    let (role, c) = match s.split_once(' ') {
+        Some((role, c)) => match parse_char(c.trim()) {
+            Some(c) => (role, c),
+            None => {
+                return Err(format!("expected one character, but got {c}"));
+            }
+        },
+        None => {
+            return Err(format!("invalid value: {s}"));
+        }
+    };
+
+    let (value, is_valid) = match role {
+        "jamo" => (KeyValue::Jamo(c), is_non_jamo(c)),
+        "cho" => (KeyValue::Cho(c), is_valid_cho(c)),
+        "joong" => (KeyValue::Joong(c), is_valid_joong(c)),
+        "jong" => (KeyValue::Jong(c), is_valid_jong(c)),
+        "char" => (KeyValue::Char(c), true),
+        _ => {
+            return Err(format!("unknown role: {role}"));
+        }
+    };
+
+    if is_valid {
+        Ok(Some(value))
+    }
+
+    else {
+        Err(format!("{} cannot be a {role}", from_v16(&[c])))
+    }
+}
@@ -1,14 +1,29 @@
+mod capi;
 mod char;
 mod constants;
 mod error;
+mod input;
+mod ipa;
+mod josa;
+mod key_event;
+mod keypad;
+mod layout;
+mod mistype;
+mod normalize;
+mod password;
+mod physical;
+mod pronounce;
 mod qwerty;
+mod romaja;
+mod romanize;
+mod search;
 mod utils;
 
 #[cfg(test)]
 mod tests;
 
 // 4352 ~ 4607
-// TODO
+// the modern ones (ᄀ~ᄒ, ᅡ~ᅵ, ᆨ~ᇂ) are handled in `normalize.rs`
 // ᄀᄁᄂᄃᄄᄅᄆᄇᄈᄉᄊᄋᄌᄍᄎᄏᄐᄑᄒᄓᄔᄕᄖᄗᄘᄙᄚᄛᄜᄝᄞᄟᄠᄡᄢᄣᄤᄥᄦᄧᄨᄩᄪᄫᄬᄭᄮᄯᄰᄱᄲᄳᄴᄵᄶᄷᄸᄹᄺᄻᄼᄽᄾᄿᅀᅁᅂᅃᅄᅅᅆᅇᅈᅉᅊᅋᅌᅍᅎᅏᅐᅑᅒᅓᅔᅕᅖᅗᅘᅙᅚᅛᅜᅝᅞᅟᅠᅡᅢᅣᅤᅥᅦᅧᅨᅩᅪᅫᅬᅭᅮᅯᅰᅱᅲᅳᅴᅵᅶᅷᅸᅹᅺᅻᅼᅽᅾᅿᆀᆁᆂᆃᆄᆅᆆᆇᆈᆉᆊᆋᆌᆍᆎᆏᆐᆑᆒᆓᆔᆕᆖᆗᆘᆙᆚᆛᆜᆝᆞᆟᆠᆡᆢᆣᆤᆥᆦᆧᆨᆩᆪᆫᆬᆭᆮᆯᆰᆱᆲᆳᆴᆵᆶᆷᆸᆹᆺᆻᆼᆽᆾᆿᇀᇁᇂᇃᇄᇅᇆᇇᇈᇉᇊᇋᇌᇍᇎᇏᇐᇑᇒᇓᇔᇕᇖᇗᇘᇙᇚᇛᇜᇝᇞᇟᇠᇡᇢᇣᇤᇥᇦᇧᇨᇩᇪᇫᇬᇭᇮᇯᇰᇱᇲᇳᇴᇵᇶᇷᇸᇹᇺᇻᇼᇽᇾᇿ
 
 // 12593~12622
@@ -43,5 +58,19 @@ mod tests;
 pub use crate::char::{KorChar, assemble, disassemble};
 pub use crate::constants::*;
 pub use crate::error::KorError;
-pub use crate::qwerty::{kor_to_qwerty, qwerty_to_kor};
-pub use crate::utils::{is_valid_cho, is_valid_consonant, is_valid_jong, is_valid_joong, is_valid_vowel, is_jamo, is_hangul, is_non_jamo, is_tensory, into_v16, from_v16};
+pub use crate::input::{HangulInput, InputContext};
+pub use crate::ipa::{IpaOptions, TenseMark, to_ipa};
+pub use crate::josa::{Josa, WithJosa, attach_josa, josa, with_josa};
+pub use crate::key_event::{CapsLock, KeyEvent};
+pub use crate::keypad::{ARAEA, KeypadContext, KeypadLayout, SSANG_ARAEA, cheonjiin_to_kor, keypad_to_kor, kor_to_cheonjiin, kor_to_keypad};
+pub use crate::layout::{KeyValue, Layout, LayoutKind, Lossless, keys_to_kor, keys_to_kor_lossless, keys_to_kor_with_offsets, kor_to_keys, kor_to_keys_with_offsets, lossless_to_keys};
+pub use crate::mistype::{Mistyped, detect_mistyped, fix_command, fix_mistyped, fix_structured, mistype_score};
+pub use crate::normalize::{compose, decompose, to_compatibility, to_conjoining_cho, to_conjoining_joong, to_conjoining_jong};
+pub use crate::password::password_candidates;
+pub use crate::physical::PhysicalLayout;
+pub use crate::pronounce::{pronounce, pronounce_chars};
+pub use crate::qwerty::{is_caps_lock_on, kor_to_qwerty, kor_to_qwerty_with_offsets, lossless_to_qwerty, qwerty_to_kor, qwerty_to_kor_lossless, qwerty_to_kor_with_caps_lock, qwerty_to_kor_with_offsets};
+pub use crate::romaja::{romaja_candidates, romaja_to_kor};
+pub use crate::romanize::{McCuneReischauer, RomanizationScheme, RomanizeMode, RomanizeOptions, Romanizer, Yale, romanize};
+pub use crate::search::{ChosungMatcher, DoubleConsonants, JamoDistance, atomic_jamo, chosung, jamo_distance, is_prefix_match, prefix_match};
+pub use crate::utils::{is_valid_cho, is_valid_consonant, is_valid_jong, is_valid_joong, is_valid_vowel, is_jamo, is_hangul, is_non_jamo, is_conjoining, is_conjoining_cho, is_conjoining_joong, is_conjoining_jong, is_tensory, invert_case, into_v16, from_v16};
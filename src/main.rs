@@ -1,16 +1,70 @@
 use std::{env, io::{self, Read, Write}, fs, thread, time};
 use h_hangul::*;
 
+#[allow(clippy::cmp_owned, clippy::println_empty_string)]
 fn main() {
     let mut args: Vec<String> = env::args().collect();
 
+    // the arguments are a command line of the user, so they are not parsed
+    if args.len() > 1 && args[1] == String::from("--command-not-found") {
+        if args.len() < 3 {
+            println!("Error: <command> is not given!");
+        }
+
+        else {
+            command_not_found(&args[2..]);
+        }
+
+        return;
+    }
+
+    let layout = match args.iter().position(|arg| arg == "-l" || arg == "--layout") {
+        Some(index) if index + 1 < args.len() => {
+            let name = args.remove(index + 1);
+            args.remove(index);
+
+            match load_layout(&name) {
+                Ok(layout) => layout,
+                Err(e) => {
+                    println!("Error: {}", e);
+                    return;
+                }
+            }
+        }
+        Some(_) => {
+            println!("Error: <layout> is not given!");
+            return;
+        }
+        None => Layout::dubeolsik(),
+    };
+
+    let physical = match args.iter().position(|arg| arg == "-p" || arg == "--physical") {
+        Some(index) if index + 1 < args.len() => {
+            let name = args.remove(index + 1);
+            args.remove(index);
+
+            match PhysicalLayout::from_name(&name) {
+                Some(physical) => physical,
+                None => {
+                    println!("Error: unknown physical layout: {}", name);
+                    return;
+                }
+            }
+        }
+        Some(_) => {
+            println!("Error: <physical_layout> is not given!");
+            return;
+        }
+        None => PhysicalLayout::Qwerty,
+    };
+
     // default behavior
     if args.len() == 1 {
         args.push(String::from("-h"));
     }
 
     if args[1] == String::from("-i") || args[1] == String::from("--interactive") {
-        interactive();
+        interactive(&layout, physical);
     }
 
     else if args[1] == String::from("-f") || args[1] == String::from("--file") {
@@ -19,7 +73,20 @@ fn main() {
         }
 
         else {
-            fileio(&args[2], &args[3]);
+            fileio(&args[2], &args[3], &layout, physical);
+        }
+    }
+
+    else if args[1] == String::from("-r") || args[1] == String::from("--romanize") {
+        let name = if args.len() < 3 { "rr" } else { &args[2] };
+
+        match RomanizationScheme::from_name(name) {
+            Some(scheme) => {
+                romanize_interactive(&scheme);
+            }
+            None => {
+                println!("Error: unknown romanization system: {}", name);
+            }
         }
     }
 
@@ -32,6 +99,21 @@ fn main() {
         println!("");
         println!("    -f  --file  <input_file>  <output_file>");
         println!("        Reads a string from <input_file> and writes the converted result to <output_file>");
+        println!("");
+        println!("    -l  --layout  <layout>");
+        println!("        Uses <layout> with -i and -f (default: dubeolsik)");
+        println!("        <layout> is a name (dubeolsik, 390, final, noshift) or a path to a layout file");
+        println!("");
+        println!("    -p  --physical  <physical_layout>");
+        println!("        The layout of the OS that typed the input: qwerty, dvorak, colemak, azerty or qwertz (default: qwerty)");
+        println!("");
+        println!("    -r  --romanize  [rr | mr | yale]");
+        println!("        romanizes Hangul in an interactive mode (default: rr)");
+        println!("");
+        println!("    --command-not-found  <command>  [arguments...]");
+        println!("        Suggests <command> typed in Korean mode (ㅣㄴ -ㅣㅁ -> ls -la), and exits with 127");
+        println!("        It's for the shell's hook, e.g. `command_not_found_handle() {{ h_hangul --command-not-found \"$@\"; }}` in bash");
+        println!("");
         println!("    -h  --help");
         println!("        Prints out this message");
         println!("");
@@ -51,7 +133,8 @@ fn main() {
 
 }
 
-fn fileio(input_path: &str, output_path: &str) {
+#[allow(clippy::single_match)]
+fn fileio(input_path: &str, output_path: &str, layout: &Layout, physical: PhysicalLayout) {
     loop {
         thread::sleep(time::Duration::from_millis(300));
 
@@ -63,7 +146,7 @@ fn fileio(input_path: &str, output_path: &str) {
             }
         };
 
-        let result = from_v16(&qwerty_to_kor(&into_v16(&input)));
+        let result = from_v16(&keys_to_kor(&physical.to_qwerty(&into_v16(&input)), layout));
 
         match write_to_file(output_path, result.as_bytes()) {
             Err(_) => {
@@ -74,13 +157,13 @@ fn fileio(input_path: &str, output_path: &str) {
     }
 }
 
-fn interactive() {
+fn interactive(layout: &Layout, physical: PhysicalLayout) {
     loop {
         let mut buf = String::new();
 
         match io::stdin().read_line(&mut buf) {
             Ok(_) => {
-                println!("{}", from_v16(&qwerty_to_kor(&into_v16(&buf))));
+                println!("{}", from_v16(&keys_to_kor(&physical.to_qwerty(&into_v16(&buf)), layout)));
             }
             _ => {
                 continue;
@@ -89,6 +172,48 @@ fn interactive() {
     }
 }
 
+fn command_not_found(command: &[String]) -> ! {
+    let command = command.join(" ");
+    let fixed = from_v16(&fix_command(&into_v16(&command)));
+
+    eprintln!("{}: command not found", command);
+
+    if fixed != command {
+        eprintln!("Did you mean: {}", fixed);
+    }
+
+    std::process::exit(127);
+}
+
+fn romanize_interactive(scheme: &RomanizationScheme) {
+    loop {
+        let mut buf = String::new();
+
+        match io::stdin().read_line(&mut buf) {
+            Ok(0) => {
+                break;
+            }
+            Ok(_) => {
+                println!("{}", from_v16(&scheme.romanize(&into_v16(buf.trim_end()))));
+            }
+            _ => {
+                continue;
+            }
+        }
+    }
+}
+
+fn load_layout(name: &str) -> Result<Layout, String> {
+    if let Some(layout) = Layout::from_name(name) {
+        return Ok(layout);
+    }
+
+    match read_string(name) {
+        Ok(definition) => Layout::parse(&definition).map_err(|e| format!("{}: {}", name, e)),
+        _ => Err(format!("unknown layout: {}", name)),
+    }
+}
+
 fn read_string(path: &str) -> Result<String, ()> {
     let mut s = String::new();
 
@@ -0,0 +1,322 @@
+use crate::normalize::compose;
+use crate::qwerty::{kor_to_qwerty, qwerty_to_kor};
+use crate::utils::*;
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mistyped {
+    /// Korean, typed while the IME was in Latin mode: dkssudgktpdy -> 안녕하세요
+    KoreanInLatinMode,
+
+    /// English, typed while the IME was in Korean mode: ㅗ디ㅣㅐ -> hello
+    LatinInKoreanMode,
+}
+
+/// 0.0 ~ 1.0, how likely `token` (a word without whitespace) is mistyped that way.
+///
+/// - KoreanInLatinMode: the result of `qwerty_to_kor` has to be complete syllables (gg -> ㅎㅎ is not enough),
+///   and the token must not look like an English word: English words have vowels, and do not have more than
+///   3 consonants in a row (world -> 재깅 is English, dkssud -> 안녕 is not). Capital letters that Dubeolsik
+///   does not use (anything but QWERTOP) make it English.
+/// - LatinInKoreanMode: the ratio of the jamo that are not in syllables (ㅗ디ㅣㅐ -> 0.75).
+///   Repeated jamo, like ㅋㅋ and ㅠㅠ, are not counted.
+pub fn mistype_score(token: &[u16], mistyped: Mistyped) -> f32 {
+    match mistyped {
+        Mistyped::KoreanInLatinMode => korean_in_latin_score(token),
+        Mistyped::LatinInKoreanMode => latin_in_korean_score(&compose(token)),
+    }
+}
+
+/// `Some` if `mistype_score` is at least 0.5
+pub fn detect_mistyped(token: &[u16]) -> Option<Mistyped> {
+    [Mistyped::KoreanInLatinMode, Mistyped::LatinInKoreanMode].into_iter().find(|m| mistype_score(token, *m) >= 0.5)
+}
+
+/// "dkssud ㅗ디ㅣㅐ 세상" -> "안녕 hello 세상"\
+/// Each token (split by whitespace) is converted only if `detect_mistyped` says so.
+pub fn fix_mistyped(string: &[u16]) -> Vec<u16> {
+    let mut result = Vec::with_capacity(string.len());
+
+    for token in tokens(string) {
+        match detect_mistyped(token) {
+            Some(Mistyped::KoreanInLatinMode) => {
+                result.extend(qwerty_to_kor(token));
+            }
+            Some(Mistyped::LatinInKoreanMode) => {
+                result.extend(kor_to_qwerty(token));
+            }
+            None => {
+                result.extend_from_slice(token);
+            }
+        }
+    }
+
+    result
+}
+
+/// "주소는 ㅈㅈㅈ.ㅎㅐㅐ히ㄷ.채ㅡ" -> "주소는 www.google.com"\
+/// "ㅣㄴ -ㅣㅁ" -> "ls -la"\
+/// It converts only the tokens that look like URLs, emails, paths and options, and only if their Hangul
+/// has loose jamo (예/아니오, 한글.txt are left as they are). Hangul right after a digit is never converted (3:30에).
+/// The first word of a line is converted too, if the line has an option,
+/// or it has the other shapes and the word is not Korean (ㅊㅇ ~/ㅇㅐ쳐ㅡ둣ㄴ -> cd ~/documents).
+pub fn fix_structured(string: &[u16]) -> Vec<u16> {
+    fix_spans(string, false)
+}
+
+/// "ㅎㅑㅅ ㄴㅅㅁ션" -> "git status"\
+/// `fix_structured`, but `string` is a shell command, so the first word is always converted,
+/// and so are the arguments that have loose jamo ("ㅎㅑㅅ 채ㅡㅡㅑㅅ -ㅡ 한글" -> "git commit -m 한글").
+pub fn fix_command(string: &[u16]) -> Vec<u16> {
+    fix_spans(string, true)
+}
+
+fn fix_spans(string: &[u16], is_command: bool) -> Vec<u16> {
+    let tokens = tokens(string);
+    let mut convert: Vec<bool> = tokens.iter().map(
+        |t| is_structured(t) || is_command && mistype_score(t, Mistyped::LatinInKoreanMode) > 0.0
+    ).collect();
+    let mut line_start = 0;
+
+    for index in 0..=tokens.len() {
+        if index < tokens.len() && !tokens[index].contains(&('\n' as u16)) {
+            continue;
+        }
+
+        if let Some(first) = (line_start..index).find(|i| !is_whitespace(tokens[*i][0])) {
+            let has_option = (first + 1..index).any(|i| is_option(tokens[i]));
+            let has_structured = (first + 1..index).any(|i| convert[i]);
+            let mistyped = detect_mistyped(tokens[first]) == Some(Mistyped::LatinInKoreanMode);
+
+            if has_hangul(tokens[first]) && (is_command || has_option || has_structured && mistyped) {
+                convert[first] = true;
+            }
+        }
+
+        line_start = index + 1;
+    }
+
+    let mut result = Vec::with_capacity(string.len());
+
+    for (token, convert) in tokens.iter().zip(convert.iter()) {
+        if *convert {
+            result.extend(convert_hangul(token));
+        }
+
+        else {
+            result.extend_from_slice(token);
+        }
+    }
+
+    result
+}
+
+// -ㅣㅁ -> -la
+fn is_option(token: &[u16]) -> bool {
+    token[0] == '-' as u16 && has_hangul(token)
+}
+
+// options, paths (/ㄷㅅㅊ, ~/, ./), URLs and emails (ㅎㅐㅐ히ㄷ.채ㅡ, ㅁ@ㅠ.채ㅡ)
+fn is_structured(token: &[u16]) -> bool {
+    let is_path = token[0] == '/' as u16 || token.starts_with(&['~' as u16, '/' as u16]) || token.starts_with(&['.' as u16, '/' as u16]);
+
+    // a separator between two words: "끝." is not a URL
+    let has_separator = (1..token.len().saturating_sub(1)).any(|i|
+        ['.', '/', '@', ':'].iter().any(|s| token[i] == *s as u16)
+        && is_word_char(token[i - 1])
+        && is_word_char(token[i + 1])
+    );
+
+    if !is_option(token) && !is_path && !has_separator {
+        return false;
+    }
+
+    // Korean also has these shapes (예/아니오, 서울.부산), so every part has to have loose jamo
+    let mut segments = hangul_segments(token).into_iter().filter(|r| !is_after_digit(token, r)).peekable();
+
+    segments.peek().is_some() && segments.all(|r| token[r].iter().any(|c| is_non_jamo(*c)))
+}
+
+// `kor_to_qwerty`, except the Hangul right after a digit
+fn convert_hangul(token: &[u16]) -> Vec<u16> {
+    let mut result = Vec::with_capacity(token.len() * 3);
+    let mut index = 0;
+
+    for segment in hangul_segments(token).into_iter() {
+        result.extend_from_slice(&token[index..segment.start]);
+
+        if is_after_digit(token, &segment) {
+            result.extend_from_slice(&token[segment.clone()]);
+        }
+
+        else {
+            result.extend(kor_to_qwerty(&token[segment.clone()]));
+        }
+
+        index = segment.end;
+    }
+
+    result.extend_from_slice(&token[index..]);
+
+    result
+}
+
+// 3:30에, v2를
+fn is_after_digit(token: &[u16], segment: &Range<usize>) -> bool {
+    segment.start > 0 && token[segment.start - 1] < 128 && (token[segment.start - 1] as u8).is_ascii_digit()
+}
+
+fn hangul_segments(token: &[u16]) -> Vec<Range<usize>> {
+    let mut result = vec![];
+    let mut index = 0;
+
+    while index < token.len() {
+        if is_jamo(token[index]) || is_non_jamo(token[index]) {
+            let start = index;
+
+            while index < token.len() && (is_jamo(token[index]) || is_non_jamo(token[index])) {
+                index += 1;
+            }
+
+            result.push(start..index);
+        }
+
+        else {
+            index += 1;
+        }
+    }
+
+    result
+}
+
+fn is_word_char(c: u16) -> bool {
+    is_jamo(c) || is_non_jamo(c) || (c < 128 && (c as u8).is_ascii_alphanumeric())
+}
+
+fn has_hangul(token: &[u16]) -> bool {
+    token.iter().any(|c| is_jamo(*c) || is_non_jamo(*c))
+}
+
+// whitespaces and non-whitespaces, alternately
+fn tokens(string: &[u16]) -> Vec<&[u16]> {
+    let mut result = vec![];
+    let mut index = 0;
+
+    while index < string.len() {
+        let end = match string[index..].iter().position(|c| is_whitespace(*c) != is_whitespace(string[index])) {
+            Some(e) => index + e,
+            None => string.len(),
+        };
+
+        result.push(&string[index..end]);
+        index = end;
+    }
+
+    result
+}
+
+fn korean_in_latin_score(token: &[u16]) -> f32 {
+    for c in token.iter() {
+        if *c >= 128 {
+            return 0.0;
+        }
+
+        let c = *c as u8;
+
+        // Dubeolsik uses Shift only for ㅃㅉㄸㄲㅆㅒㅖ
+        if c.is_ascii_uppercase() && !b"QWERTOP".contains(&c) {
+            return 0.0;
+        }
+    }
+
+    let kor = qwerty_to_kor(token);
+    let (syllables, loose) = count_hangul(&kor, false);
+
+    if syllables + loose == 0 {
+        0.0
+    }
+
+    else if loose > 0 {
+        0.5 * syllables as f32 / (syllables + loose) as f32
+    }
+
+    else if is_english_shape(token) {
+        0.4
+    }
+
+    else {
+        1.0
+    }
+}
+
+fn latin_in_korean_score(token: &[u16]) -> f32 {
+    let (syllables, loose) = count_hangul(token, true);
+
+    if syllables + loose == 0 || token.iter().any(|c| *c < 128 && (*c as u8).is_ascii_alphanumeric()) {
+        0.0
+    }
+
+    else {
+        loose as f32 / (syllables + loose) as f32
+    }
+}
+
+// (syllables, jamo that are not in syllables)
+// with `repeated_as_syllables`, the repeated jamo (ㅋㅋ) are counted as syllables
+fn count_hangul(string: &[u16], repeated_as_syllables: bool) -> (usize, usize) {
+    let mut syllables = 0;
+    let mut loose = 0;
+
+    for (index, c) in string.iter().enumerate() {
+        if is_jamo(*c) {
+            syllables += 1;
+        }
+
+        else if is_non_jamo(*c) {
+            let repeated = repeated_as_syllables && ((index > 0 && string[index - 1] == *c) || string.get(index + 1) == Some(c));
+
+            if repeated {
+                syllables += 1;
+            }
+
+            else {
+                loose += 1;
+            }
+        }
+    }
+
+    (syllables, loose)
+}
+
+// it has a vowel, and at most 3 consonants in a row: world (O), dkssud (X), gksrmf (X)
+fn is_english_shape(token: &[u16]) -> bool {
+    let mut has_vowel = false;
+    let mut consonants = 0;
+
+    for c in token.iter() {
+        let c = (*c as u8).to_ascii_lowercase();
+
+        if !c.is_ascii_alphabetic() {
+            consonants = 0;
+        }
+
+        else if b"aeiouy".contains(&c) {
+            has_vowel = true;
+            consonants = 0;
+        }
+
+        else {
+            consonants += 1;
+
+            if consonants > 3 {
+                return false;
+            }
+        }
+    }
+
+    has_vowel
+}
+
+fn is_whitespace(c: u16) -> bool {
+    char::from_u32(c as u32).map(|c| c.is_whitespace()).unwrap_or(false)
+}
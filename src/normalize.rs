@@ -0,0 +1,111 @@
+use crate::constants::*;
+use crate::utils::*;
+
+// ᄀ (U+1100), the first modern cho-sung of the conjoining block
+const CONJOINING_CHO: u16 = 4352;
+
+// ᅡ (U+1161), the first modern joong-sung of the conjoining block
+const CONJOINING_JOONG: u16 = 4449;
+
+// ᆨ (U+11A8), the first modern jong-sung of the conjoining block
+const CONJOINING_JONG: u16 = 4520;
+
+/// "ᄒ ᅡ ᆫ" (NFD) -> "한" (NFC)\
+/// It follows the composition algorithm of the Unicode Standard (section 3.12).
+/// Characters that cannot be composed are left as they are.
+pub fn compose(string: &[u16]) -> Vec<u16> {
+    let mut result: Vec<u16> = Vec::with_capacity(string.len());
+
+    for c in string.iter() {
+        let last = match result.last() {
+            Some(l) => *l,
+            _ => {
+                result.push(*c);
+                continue;
+            }
+        };
+
+        // L + V -> LV
+        if is_conjoining_cho(last) && is_conjoining_joong(*c) {
+            let cho = last - CONJOINING_CHO;
+            let joong = *c - CONJOINING_JOONG;
+
+            result.pop();
+            result.push(44032 + cho * 588 + joong * 28);
+        }
+
+        // LV + T -> LVT
+        else if is_jamo(last) && (last - 44032) % 28 == 0 && is_conjoining_jong(*c) {
+            let jong = *c - CONJOINING_JONG + 1;
+
+            result.pop();
+            result.push(last + jong);
+        }
+
+        else {
+            result.push(*c);
+        }
+    }
+
+    result
+}
+
+/// "한" (NFC) -> "ᄒ ᅡ ᆫ" (NFD)\
+/// Only the precomposed syllables are decomposed. The compatibility jamo (ㄱ, ㅏ, ...) are left as they are.
+pub fn decompose(string: &[u16]) -> Vec<u16> {
+    let mut result = Vec::with_capacity(string.len() * 3);
+
+    for c in string.iter() {
+        if is_jamo(*c) {
+            let index = *c - 44032;
+
+            result.push(CONJOINING_CHO + index / 588);
+            result.push(CONJOINING_JOONG + index % 588 / 28);
+
+            if index % 28 != 0 {
+                result.push(CONJOINING_JONG + index % 28 - 1);
+            }
+        }
+
+        else {
+            result.push(*c);
+        }
+    }
+
+    result
+}
+
+/// ᄀ -> Some(ㄱ), ᅡ -> Some(ㅏ), ᆪ -> Some(ㄳ)\
+/// Archaic jamo (ᄓ, ᆞ, ...) do not have a compatibility jamo in `CONSONANTS` and `VOWELS`, so it returns `None`.
+pub fn to_compatibility(c: u16) -> Option<u16> {
+    if is_conjoining_cho(c) {
+        Some(CHOS[(c - CONJOINING_CHO) as usize])
+    }
+
+    else if is_conjoining_joong(c) {
+        Some(JOONGS[(c - CONJOINING_JOONG) as usize])
+    }
+
+    else if is_conjoining_jong(c) {
+        Some(JONGS[(c - CONJOINING_JONG) as usize])
+    }
+
+    else {
+        None
+    }
+}
+
+/// ㄱ -> Some(ᄀ), ㄳ -> None
+pub fn to_conjoining_cho(c: u16) -> Option<u16> {
+    CHOS.iter().position(|cho| *cho == c).map(|i| CONJOINING_CHO + i as u16)
+}
+
+/// ㅏ -> Some(ᅡ)
+pub fn to_conjoining_joong(c: u16) -> Option<u16> {
+    JOONGS.iter().position(|joong| *joong == c).map(|i| CONJOINING_JOONG + i as u16)
+}
+
+/// ㄳ -> Some(ᆪ), ㄸ -> None
+pub fn to_conjoining_jong(c: u16) -> Option<u16> {
+    JONGS.iter().position(|jong| *jong == c).map(|i| CONJOINING_JONG + i as u16)
+}
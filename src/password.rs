@@ -0,0 +1,58 @@
+use crate::input::InputContext;
+use crate::qwerty::{kor_to_qwerty, qwerty_to_kor};
+use crate::utils::invert_case;
+
+/// "rkskek" -> ["rkskek", 가나다, "ㄱㅏㄴㅏㄷㅏ", 가나, "RKSKEK", 까나따, ...]\
+/// The strings that the user might have meant, when `password` was typed with the wrong IME mode or Caps Lock.
+/// It's for checking the hashes of the alternatives, so it does not have duplicates, and `password` is the first one.
+///
+/// - `qwerty_to_kor` and `kor_to_qwerty`
+/// - Caps Lock, before and after the conversion
+/// - the states that a Hangul IME might have sent: the jamo that are not composed (ㄱㅏ, when the app
+///   does not support the composition), and the text without the last syllable (가나, when the preedit is not committed)
+pub fn password_candidates(password: &[u16]) -> Vec<Vec<u16>> {
+    let mut result = vec![];
+
+    for typed in [password.to_vec(), invert_case(password)] {
+        let kor = kor_to_qwerty(&typed);
+
+        for candidate in [
+            typed.clone(),
+            qwerty_to_kor(&typed),
+            not_composed(&typed),
+            not_committed(&typed),
+            invert_case(&kor),
+            kor,
+        ] {
+            if !result.contains(&candidate) {
+                result.push(candidate);
+            }
+        }
+    }
+
+    result
+}
+
+// "rk" -> "ㄱㅏ"
+fn not_composed(keys: &[u16]) -> Vec<u16> {
+    keys.iter().flat_map(|key| qwerty_to_kor(&[*key])).collect()
+}
+
+// "rksk" -> 가, the preedit (나) is lost
+fn not_committed(keys: &[u16]) -> Vec<u16> {
+    let mut context = InputContext::new();
+    let mut result = Vec::with_capacity(keys.len());
+
+    for key in keys.iter() {
+        if context.process(*key) {
+            result.extend(context.commit());
+        }
+
+        else {
+            result.extend(context.flush());
+            result.push(*key);
+        }
+    }
+
+    result
+}
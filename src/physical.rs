@@ -0,0 +1,94 @@
+// The keys of a US QWERTY keyboard, from the top row to the bottom row.
+// The other layouts are written in the same order: the n-th character of a layout is on the n-th key.
+const QWERTY: &str = "`1234567890-=qwertyuiop[]\\asdfghjkl;'zxcvbnm,./";
+const QWERTY_SHIFTED: &str = "~!@#$%^&*()_+QWERTYUIOP{}|ASDFGHJKL:\"ZXCVBNM<>?";
+
+const DVORAK: &str = "`1234567890[]',.pyfgcrl/=\\aoeuidhtns-;qjkxbmwvz";
+const DVORAK_SHIFTED: &str = "~!@#$%^&*(){}\"<>PYFGCRL?+|AOEUIDHTNS_:QJKXBMWVZ";
+
+const COLEMAK: &str = "`1234567890-=qwfpgjluy;[]\\arstdhneio'zxcvbkm,./";
+const COLEMAK_SHIFTED: &str = "~!@#$%^&*()_+QWFPGJLUY:{}|ARSTDHNEIO\"ZXCVBKM<>?";
+
+// French
+const AZERTY: &str = "²&é\"'(-è_çà)=azertyuiop^$*qsdfghjklmùwxcvbn,;:!";
+const AZERTY_SHIFTED: &str = "³1234567890°+AZERTYUIOP¨£µQSDFGHJKLM%WXCVBN?./§";
+
+// German
+const QWERTZ: &str = "^1234567890ß´qwertzuiopü+#asdfghjklöäyxcvbnm,.-";
+const QWERTZ_SHIFTED: &str = "°!\"§$%&/()=?`QWERTZUIOPÜ*'ASDFGHJKLÖÄYXCVBNM;:_";
+
+/// The layout of the keyboard that the OS uses. Dubeolsik is position-based, so when a text is typed
+/// in Korean mode, but the OS produces Latin characters, the characters depend on this layout.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PhysicalLayout {
+    Qwerty,
+    Dvorak,
+    Colemak,
+
+    /// French
+    Azerty,
+
+    /// German
+    Qwertz,
+}
+
+impl PhysicalLayout {
+    /// "qwerty", "dvorak", "colemak", "azerty" or "qwertz"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "qwerty" => Some(PhysicalLayout::Qwerty),
+            "dvorak" => Some(PhysicalLayout::Dvorak),
+            "colemak" => Some(PhysicalLayout::Colemak),
+            "azerty" => Some(PhysicalLayout::Azerty),
+            "qwertz" => Some(PhysicalLayout::Qwertz),
+            _ => None,
+        }
+    }
+
+    /// "itopmu" (Dvorak) -> "gksrmf"\
+    /// It finds the keys that typed `string`, and returns what the keys type on QWERTY.
+    /// The characters that are not on the keyboard are left as they are.
+    pub fn to_qwerty(&self, string: &[u16]) -> Vec<u16> {
+        let (keys, shifted) = self.keys();
+
+        remap(string, (keys, shifted), (QWERTY, QWERTY_SHIFTED))
+    }
+
+    /// "gksrmf" -> "itopmu" (Dvorak)\
+    /// the reverse of `to_qwerty`
+    pub fn from_qwerty(&self, string: &[u16]) -> Vec<u16> {
+        let (keys, shifted) = self.keys();
+
+        remap(string, (QWERTY, QWERTY_SHIFTED), (keys, shifted))
+    }
+
+    fn keys(&self) -> (&'static str, &'static str) {
+        match self {
+            PhysicalLayout::Qwerty => (QWERTY, QWERTY_SHIFTED),
+            PhysicalLayout::Dvorak => (DVORAK, DVORAK_SHIFTED),
+            PhysicalLayout::Colemak => (COLEMAK, COLEMAK_SHIFTED),
+            PhysicalLayout::Azerty => (AZERTY, AZERTY_SHIFTED),
+            PhysicalLayout::Qwertz => (QWERTZ, QWERTZ_SHIFTED),
+        }
+    }
+}
+
+fn remap(string: &[u16], from: (&str, &str), to: (&str, &str)) -> Vec<u16> {
+    let from: Vec<u16> = from.0.chars().chain(from.1.chars()).map(|c| c as u16).collect();
+    let to: Vec<u16> = to.0.chars().chain(to.1.chars()).map(|c| c as u16).collect();
+
+    let mut result = Vec::with_capacity(string.len());
+
+    for c in string.iter() {
+        match from.iter().position(|c_| c_ == c) {
+            Some(i) => {
+                result.push(to[i]);
+            }
+            None => {
+                result.push(*c);
+            }
+        }
+    }
+
+    result
+}
@@ -0,0 +1,249 @@
+use crate::char::{KorChar, assemble, disassemble};
+use crate::normalize::compose;
+use crate::utils::*;
+
+/// 국물 -> 궁물, 신라 -> 실라, 좋다 -> 조타 (표준 발음법)\
+/// It applies the liaison, nasalization, lateralization, palatalization, aspiration and tensification
+/// between syllables, and the jong-sungs are neutralized to the 7 representative sounds (ㄱ, ㄴ, ㄷ, ㄹ, ㅁ, ㅂ, ㅇ).
+/// The sound changes do not cross non-syllable characters, including whitespaces.
+/// Rules that depend on the meaning of words (사잇소리, 밟다, ...) are not implemented.
+pub fn pronounce(string: &[u16]) -> Vec<u16> {
+    let string = compose(string);
+    let mut result = Vec::with_capacity(string.len());
+    let mut index = 0;
+
+    while index < string.len() {
+        if is_jamo(string[index]) {
+            let mut word = vec![];
+
+            while index < string.len() && is_jamo(string[index]) {
+                word.push(KorChar::from_u16(string[index]).unwrap());
+                index += 1;
+            }
+
+            result.extend(pronounce_chars(&word).iter().map(|c| c.to_u16()));
+        }
+
+        else {
+            result.push(string[index]);
+            index += 1;
+        }
+    }
+
+    result
+}
+
+/// [KorChar(국), KorChar(물)] -> [KorChar(궁), KorChar(물)]\
+/// Characters that are not complete syllables (ㄱ, ㅏ, ...) separate words.
+pub fn pronounce_chars(chars: &[KorChar]) -> Vec<KorChar> {
+    let mut result = chars.to_vec();
+
+    // `KorChar::is_valid` panics on bare jamo
+    for word in result.split_mut(|c| !matches!(c.disassemble(), (Some(_), Some(_), _))) {
+        apply_sound_changes(word, true);
+
+        for c in word.iter_mut() {
+            let (_, _, jong) = c.disassemble();
+
+            if let Some(j) = jong {
+                *c = c.set_jong(Some(neutralize(simplify(j)))).unwrap();
+            }
+        }
+    }
+
+    result
+}
+
+// sound changes between the syllables of a word\
+// The romanization systems do not reflect the tensification (경음화), so it's optional.
+pub(crate) fn apply_sound_changes(word: &mut [KorChar], tensification: bool) {
+    for index in 1..word.len() {
+        let (c1, v1, j1) = word[index - 1].disassemble();
+        let (_, v2, _) = word[index].disassemble();
+        let c2 = word[index].disassemble().0.unwrap();
+
+        let (new_j1, mut new_c2) = match j1 {
+            Some(j) => boundary(j, c2, v2.unwrap()),
+            None => continue,
+        };
+
+        // 국밥 -> 국빱, 값이 -> 갑씨
+        if tensification {
+            if let Some(j) = new_j1 {
+                if is_obstruent(neutralize(simplify(j))) {
+                    new_c2 = tensify(new_c2);
+                }
+            }
+        }
+
+        word[index - 1] = KorChar::combine(c1, v1, new_j1).unwrap();
+        word[index] = word[index].set_cho(new_c2).unwrap();
+    }
+}
+
+// (jong of the previous syllable, cho of the next syllable, joong of the next syllable)
+fn boundary(jong: u16, cho: u16, joong: u16) -> (Option<u16>, u16) {
+    // 연음: 옷이 -> 오시, 않아 -> 아나, 밖에 -> 바께
+    if cho == 'ㅇ' as u16 {
+        return if jong == 'ㅇ' as u16 {
+            (Some(jong), cho)
+        } else if jong == 'ㅎ' as u16 {
+            (None, cho)
+        } else if jong == 'ㄶ' as u16 {
+            (None, 'ㄴ' as u16)
+        } else if jong == 'ㅀ' as u16 {
+            (None, 'ㄹ' as u16)
+        } else if is_tensory(jong) {
+            (None, jong)
+        } else {
+            match disassemble(jong) {
+                Some((j1, j2)) => (Some(j1), palatalize(j2, joong)),
+                None => (None, palatalize(jong, joong)),
+            }
+        };
+    }
+
+    let mut jong = jong;
+
+    // 좋다 -> 조타, 않고 -> 안코, 놓는 -> 논는
+    if jong == 'ㅎ' as u16 || jong == 'ㄶ' as u16 || jong == 'ㅀ' as u16 {
+        let rest = disassemble(jong).map(|(j1, _)| j1);
+
+        match aspirate(cho) {
+            Some(c) => {
+                return (rest, c);
+            }
+            _ if cho == 'ㅅ' as u16 => {
+                return (rest, 'ㅆ' as u16);
+            }
+            _ => {
+                jong = rest.unwrap_or('ㄷ' as u16);
+            }
+        }
+    }
+
+    // 밝히다 -> 발키다, 앉히다 -> 안치다, 잡히다 -> 자피다
+    if cho == 'ㅎ' as u16 {
+        match disassemble(jong) {
+            Some((j1, j2)) if !is_tensory(jong) && aspirate(j2).is_some() => {
+                return (Some(j1), aspirate(j2).unwrap());
+            }
+            _ => {}
+        }
+
+        let simple = neutralize(simplify(jong));
+
+        if jong == 'ㄷ' as u16 && joong == 'ㅣ' as u16 {
+            return (None, 'ㅊ' as u16);
+        }
+
+        if jong == 'ㅈ' as u16 || jong == 'ㅊ' as u16 {
+            return (None, 'ㅊ' as u16);
+        }
+
+        if let Some(c) = aspirate(simple) {
+            return (None, c);
+        }
+    }
+
+    let jong = simplify(jong);
+    let neutral = neutralize(jong);
+    let mut cho = cho;
+
+    // 종로 -> 종노, 백로 -> 백노 -> 뱅노
+    if cho == 'ㄹ' as u16 && neutral != 'ㄴ' as u16 && neutral != 'ㄹ' as u16 {
+        cho = 'ㄴ' as u16;
+    }
+
+    // 신라 -> 실라, 칼날 -> 칼랄
+    if (neutral == 'ㄴ' as u16 && cho == 'ㄹ' as u16) || (neutral == 'ㄹ' as u16 && cho == 'ㄴ' as u16) {
+        return (Some('ㄹ' as u16), 'ㄹ' as u16);
+    }
+
+    // 국물 -> 궁물, 받는 -> 반는, 밥먹다 -> 밤먹다
+    if cho == 'ㄴ' as u16 || cho == 'ㅁ' as u16 {
+        let nasal = if neutral == 'ㄱ' as u16 {
+            Some('ㅇ' as u16)
+        } else if neutral == 'ㄷ' as u16 {
+            Some('ㄴ' as u16)
+        } else if neutral == 'ㅂ' as u16 {
+            Some('ㅁ' as u16)
+        } else {
+            None
+        };
+
+        if let Some(n) = nasal {
+            return (Some(n), cho);
+        }
+    }
+
+    (Some(jong), cho)
+}
+
+// 굳이 -> 구지, 같이 -> 가치
+fn palatalize(cho: u16, joong: u16) -> u16 {
+    if joong != 'ㅣ' as u16 {
+        cho
+    } else if cho == 'ㄷ' as u16 {
+        'ㅈ' as u16
+    } else if cho == 'ㅌ' as u16 {
+        'ㅊ' as u16
+    } else {
+        cho
+    }
+}
+
+// ㄱ -> ㅋ, ㄷ -> ㅌ, ㅂ -> ㅍ, ㅈ -> ㅊ
+fn aspirate(c: u16) -> Option<u16> {
+    if c == 'ㄱ' as u16 {
+        Some('ㅋ' as u16)
+    } else if c == 'ㄷ' as u16 {
+        Some('ㅌ' as u16)
+    } else if c == 'ㅂ' as u16 {
+        Some('ㅍ' as u16)
+    } else if c == 'ㅈ' as u16 {
+        Some('ㅊ' as u16)
+    } else {
+        None
+    }
+}
+
+// double finals before a consonant: ㄳ -> ㄱ, ㄻ -> ㅁ, ㄼ -> ㄹ, ...
+fn simplify(jong: u16) -> u16 {
+    match disassemble(jong) {
+        Some(_) if is_tensory(jong) => jong,
+        Some((j1, j2)) => {
+            // ㄺ, ㄻ, ㄿ keep the second consonant
+            if jong == 'ㄺ' as u16 || jong == 'ㄻ' as u16 || jong == 'ㄿ' as u16 {
+                j2
+            } else {
+                j1
+            }
+        }
+        None => jong,
+    }
+}
+
+// ㄲ, ㅋ -> ㄱ / ㅅ, ㅆ, ㅈ, ㅊ, ㅌ, ㅎ -> ㄷ / ㅍ -> ㅂ
+fn neutralize(jong: u16) -> u16 {
+    match char::from_u32(jong as u32).unwrap() {
+        'ㄲ' | 'ㅋ' => 'ㄱ' as u16,
+        'ㅅ' | 'ㅆ' | 'ㅈ' | 'ㅊ' | 'ㅌ' | 'ㅎ' => 'ㄷ' as u16,
+        'ㅍ' => 'ㅂ' as u16,
+        _ => jong,
+    }
+}
+
+// ㄱ, ㄷ, ㅂ: the representative sounds of the obstruents
+fn is_obstruent(jong: u16) -> bool {
+    jong == 'ㄱ' as u16 || jong == 'ㄷ' as u16 || jong == 'ㅂ' as u16
+}
+
+// ㄱ -> ㄲ, ㄷ -> ㄸ, ㅂ -> ㅃ, ㅅ -> ㅆ, ㅈ -> ㅉ
+fn tensify(cho: u16) -> u16 {
+    match assemble(cho, cho) {
+        Some(c) if is_tensory(c) => c,
+        _ => cho,
+    }
+}
+
@@ -1,296 +1,60 @@
-use crate::constants::*;
-use crate::*;
-
-enum ParseState {
-    None,
-    Cho(u16),
-    Joong(u16, u16),
-    Jong(u16, u16, u16)
-}
+use crate::key_event::CapsLock;
+use crate::layout::{DUBEOLSIK, Lossless, keys_to_kor, keys_to_kor_lossless, keys_to_kor_with_offsets, kor_to_keys, kor_to_keys_with_offsets, lossless_to_keys};
+use crate::utils::{invert_case, is_jamo, is_non_jamo};
+use std::ops::Range;
 
 /// qogusthf -> 배현솔
 pub fn qwerty_to_kor(string: &[u16]) -> Vec<u16> {
-    let mut result = Vec::with_capacity(string.len());
-    let mut curr_parse_state = ParseState::None;
-    let mut index = 0;
-
-    while index <= string.len() {
-        let q = if index < string.len() {
-            string[index]
-        } else {
-            ' ' as u16  // it handles the last hangul
-        };
-        index += 1;
-
-        let next_char = match QWERTY_TO_KOR.get(&q) {
-            Some(k) => k,
-            _ => &q
-        };
-
-        match curr_parse_state {
-            ParseState::None => {
-                if is_valid_consonant(*next_char) {
-                    curr_parse_state = ParseState::Cho(*next_char);
-                }
-
-                // ml -> ㅢ
-                else if is_valid_vowel(*next_char) && index > 1 && is_valid_vowel(result[result.len() - 1]) {
-                    match assemble(result[result.len() - 1], *next_char) {
-                        Some(v) => {
-                            result.pop();
-                            result.push(v);
-                        }
-                        _ => {
-                            result.push(*next_char);
-                        }
-                    }
-                }
-
-                else {
-                    result.push(*next_char);
-                }
-            }
-            ParseState::Cho(c) => {
-                if is_valid_consonant(*next_char) {
-                    match assemble(c, *next_char) {
-                        // `ㄱㄱ` is `rr`, and `ㄲ` is `R` -> that means `rr` is not `ㄲ`
-                        Some(new_c) if !is_tensory(new_c) => {
-                            curr_parse_state = ParseState::Cho(new_c);
-                        }
-                        _ => {
-                            result.push(c);
-                            curr_parse_state = ParseState::Cho(*next_char);
-                        }
-                    }
-                }
-
-                else if is_valid_vowel(*next_char) {
-                    curr_parse_state = ParseState::Joong(c, *next_char);
-                }
-
-                else {
-                    result.push(c);
-                    result.push(*next_char);
-                    curr_parse_state = ParseState::None;
-                }
-            }
-            ParseState::Joong(c, j) => {
-                if is_valid_consonant(*next_char) {
-                    curr_parse_state = ParseState::Jong(c, j, *next_char);
-                }
-
-                else if is_valid_vowel(*next_char) {
-                    match assemble(j, *next_char) {
-                        Some(new_v) => {
-                            curr_parse_state = ParseState::Joong(c, new_v);
-                        },
-                        _ => {
-                            match KorChar::combine(Some(c), Some(j), None) {
-                                Ok(h) => {
-                                    result.push(h.to_u16());
-                                }
-                                _ => {
-                                    result.push(c);
-                                    result.push(j);
-                                }
-                            }
-
-                            result.push(*next_char);
-                            curr_parse_state = ParseState::None;
-                        }
-                    }
-                }
-
-                else {
-                    match KorChar::combine(Some(c), Some(j), None) {
-                        Ok(h) => {
-                            result.push(h.to_u16());
-                        }
-                        _ => {
-                            let (c1, c2) = disassemble(c).unwrap();
-                            let h = KorChar::combine(Some(c2), Some(j), None).unwrap();
-
-                            result.push(c1);
-                            result.push(h.to_u16());
-                        }
-                    }
-
-                    result.push(*next_char);
-                    curr_parse_state = ParseState::None;
-                }
-
-            }
-            ParseState::Jong(c, ju, jo) => {
-                if is_valid_consonant(*next_char) {
-                    match assemble(jo, *next_char) {
-                        Some(new_c) if !is_tensory(new_c) => {
-                            curr_parse_state = ParseState::Jong(c, ju, new_c);
-                        }
-                        _ => {
-                            match KorChar::combine(Some(c), Some(ju), Some(jo)) {
-                                Ok(h) => {
-                                    result.push(h.to_u16());
-                                }
-                                _ => match KorChar::combine(Some(c), Some(ju), None) {
-                                    Ok(h) => {
-                                        result.push(h.to_u16());
-                                        result.push(jo);
-                                    }
-                                    _ => {
-                                        result.push(c);
-                                        result.push(ju);
-                                        result.push(jo);
-                                    }
-                                }
-                            }
-
-                            curr_parse_state = ParseState::Cho(*next_char);
-                        }
-                    }
-                }
-
-                else if is_valid_vowel(*next_char) {
-                    match disassemble(jo) {
-                        Some((c1, c2)) if !is_tensory(jo) => {
-
-                            match KorChar::combine(Some(c), Some(ju), Some(c1)) {
-                                Ok(h) => {
-                                    result.push(h.to_u16());
-                                }
-                                // ( c )    ju    c1    c2
-                                // ㄱ ㅅ    ㅣ    ㄱ     ㅅ
-                                _ => {
-                                    let (c3, c4) = disassemble(c).unwrap();
-                                    let h = KorChar::combine(Some(c4), Some(ju), Some(c1)).unwrap();
-
-                                    result.push(c3);
-                                    result.push(h.to_u16());
-                                }
-                            }
-
-                            curr_parse_state = ParseState::Joong(c2, *next_char);
-                        }
-                        _ => {
-                            match KorChar::combine(Some(c), Some(ju), None) {
-                                Ok(h) => {
-                                    result.push(h.to_u16());
-                                }
-                                // ( c )    ju    jo
-                                // ㄱ ㅅ    ㅣ    ㄷ
-                                _ => {
-                                    let (c1, c2) = disassemble(c).unwrap();
-                                    let h = KorChar::combine(Some(c2), Some(ju), None).unwrap();
-
-                                    result.push(c1);
-                                    result.push(h.to_u16());
-                                }
-                            }
+    keys_to_kor(string, &DUBEOLSIK)
+}
 
-                            curr_parse_state = ParseState::Joong(jo, *next_char);
-                        }
-                    }
-                }
+/// 배현솔 -> qogusthf\
+/// The conjoining jamo (NFD) are composed before the conversion.
+pub fn kor_to_qwerty(string: &[u16]) -> Vec<u16> {
+    kor_to_keys(string, &DUBEOLSIK)
+}
 
-                else {
-                    match KorChar::combine(Some(c), Some(ju), Some(jo)) {
-                        Ok(h) => {
-                            result.push(h.to_u16());
-                        }
-                        /*
-                         *          ㅆ      ㄸ       ㄳ       ㄱ
-                         *  ㅆ      쌌      땄      ㄱ샀      갔
-                         *  ㄸ      싸ㄸ    따ㄸ     ㄱ사ㄸ    가ㄸ
-                         *  ㄳ      싻      딳       ㄱ삯     갃
-                         *  ㄱ      싹      딱       ㄱ삭     각
-                         */
-                        _ => if is_tensory(jo) {
-                            match KorChar::combine(Some(c), Some(ju), None) {
-                                Ok(h) => {
-                                    result.push(h.to_u16());
-                                    result.push(jo);
-                                }
-                                _ => {
-                                    let (c1, c2) = disassemble(c).unwrap();
-                                    let h = KorChar::combine(Some(c2), Some(ju), None).unwrap();
+/// "dkssud" -> (안녕, [0..3, 3..6])\
+/// `qwerty_to_kor`, with the range of the keys that typed each character of the result
+pub fn qwerty_to_kor_with_offsets(string: &[u16]) -> (Vec<u16>, Vec<Range<usize>>) {
+    keys_to_kor_with_offsets(string, &DUBEOLSIK)
+}
 
-                                    result.push(c1);
-                                    result.push(h.to_u16());
-                                    result.push(jo);
-                                }
-                            }
+/// 안녕 -> ("dkssud", [0..1, 0..1, 0..1, 1..2, 1..2, 1..2])\
+/// `kor_to_qwerty`, with the range of the characters that typed each key
+pub fn kor_to_qwerty_with_offsets(string: &[u16]) -> (Vec<u16>, Vec<Range<usize>>) {
+    kor_to_keys_with_offsets(string, &DUBEOLSIK)
+}
 
-                        } else {
-                            let (c1, c2) = disassemble(c).unwrap();
-                            let h = KorChar::combine(Some(c2), Some(ju), Some(jo)).unwrap();
+/// "dKssud" -> Lossless { text: 안녕, patches: [(0..1, "dKs")] }\
+/// `qwerty_to_kor`, with the keys that `kor_to_qwerty` cannot restore
+pub fn qwerty_to_kor_lossless(string: &[u16]) -> Lossless {
+    keys_to_kor_lossless(string, &DUBEOLSIK)
+}
 
-                            result.push(c1);
-                            result.push(h.to_u16());
-                        }
-                    }
+/// the reverse of `qwerty_to_kor_lossless`
+pub fn lossless_to_qwerty(lossless: &Lossless) -> Vec<u16> {
+    lossless_to_keys(lossless, &DUBEOLSIK)
+}
 
-                    result.push(*next_char);
-                    curr_parse_state = ParseState::None;
-                }
-            }
-        }
+/// "QKQ", CapsLock::Invert -> 밥\
+/// `qwerty_to_kor`, for the keys typed with Caps Lock on. With `CapsLock::Invert`, the case of
+/// the letters is inverted before the conversion (ㅃㅉㄸㄲㅆㅒㅖ -> ㅂㅈㄷㄱㅅㅐㅔ).
+pub fn qwerty_to_kor_with_caps_lock(string: &[u16], caps_lock: CapsLock) -> Vec<u16> {
+    match caps_lock {
+        CapsLock::Ignore => qwerty_to_kor(string),
+        CapsLock::Invert => qwerty_to_kor(&invert_case(string)),
     }
-
-    // pops the extra whitespace
-    result.pop().unwrap();
-
-    result
 }
 
-/// 배현솔 -> qogusthf
-pub fn kor_to_qwerty(string: &[u16]) -> Vec<u16> {
-    let mut result = Vec::with_capacity(string.len() * 3);
-
-    for c in string.iter() {
-        if is_non_jamo(*c) {
-            match KOR_TO_QWERTY.get(c) {
-                Some(q) => {
-                    result.push(*q);
-                }
-                _ => match disassemble(*c) {
-                    Some((c1, c2)) => {
-                        result.push(*KOR_TO_QWERTY.get(&c1).unwrap());
-                        result.push(*KOR_TO_QWERTY.get(&c2).unwrap());
-                    },
-                    _ => {
-                        unreachable!();
-                    }
-                }
-            }
-        }
-
-        else if is_jamo(*c) {
-            let (cho, joong, jong) = KorChar::from_u16(*c).unwrap().disassemble();
-
-            for c in [cho, joong, jong] {
-                match c {
-                    Some(c_) => match KOR_TO_QWERTY.get(&c_) {
-                        Some(q) => {
-                            result.push(*q);
-                        }
-                        _ => match disassemble(c_) {
-                            Some((c1, c2)) => {
-                                result.push(*KOR_TO_QWERTY.get(&c1).unwrap());
-                                result.push(*KOR_TO_QWERTY.get(&c2).unwrap());
-                            }
-                            _ => {
-                                unreachable!();
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-
-        else {
-            result.push(*c);
-        }
-    }
+/// "DKSSUD", "QKQ" -> true\
+/// "dkssud", "Qkq", "OK", "Hello WORLD" -> false\
+/// Whether `string` looks like it was typed with Caps Lock on. The inverted `string` has to be complete
+/// syllables, and `string` has to be not: either it is not complete syllables (QKQ -> 빠ㅃ), or it has
+/// capital letters that Dubeolsik does not use (anything but QWERTOP).
+pub fn is_caps_lock_on(string: &[u16]) -> bool {
+    let is_complete = |kor: &[u16]| kor.iter().any(|c| is_jamo(*c)) && !kor.iter().any(|c| is_non_jamo(*c));
+    let unused_capitals = string.iter().any(|c| *c < 128 && (*c as u8).is_ascii_uppercase() && !b"QWERTOP".contains(&(*c as u8)));
 
-    result
+    is_complete(&qwerty_to_kor(&invert_case(string))) && (!is_complete(&qwerty_to_kor(string)) || unused_capitals)
 }
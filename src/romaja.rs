@@ -0,0 +1,332 @@
+use crate::char::KorChar;
+
+// (spelling, as a cho-sung, as a jong-sung, whether it's the Revised Romanization)
+const CONSONANTS: [(&str, Option<char>, Option<char>, bool); 27] = [
+    ("g", Some('ㄱ'), Some('ㄱ'), true),
+    ("k", Some('ㅋ'), Some('ㄱ'), true),
+    ("kk", Some('ㄲ'), Some('ㄲ'), true),
+    ("n", Some('ㄴ'), Some('ㄴ'), true),
+    ("d", Some('ㄷ'), Some('ㄷ'), true),
+    ("t", Some('ㅌ'), Some('ㅅ'), true),
+    ("tt", Some('ㄸ'), None, true),
+    ("r", Some('ㄹ'), Some('ㄹ'), true),
+    ("l", Some('ㄹ'), Some('ㄹ'), true),
+    ("m", Some('ㅁ'), Some('ㅁ'), true),
+    ("b", Some('ㅂ'), Some('ㅂ'), true),
+    ("p", Some('ㅍ'), Some('ㅂ'), true),
+    ("pp", Some('ㅃ'), None, true),
+    ("s", Some('ㅅ'), Some('ㅅ'), true),
+    ("ss", Some('ㅆ'), Some('ㅆ'), true),
+    ("j", Some('ㅈ'), Some('ㅈ'), true),
+    ("jj", Some('ㅉ'), None, true),
+    ("ch", Some('ㅊ'), Some('ㅊ'), true),
+    ("h", Some('ㅎ'), Some('ㅎ'), true),
+    ("ng", None, Some('ㅇ'), true),
+
+    // the McCune-Reischauer readings (kimchi -> 김치), and letters that people type anyway
+    ("k", Some('ㄱ'), None, false),
+    ("t", Some('ㄷ'), None, false),
+    ("p", Some('ㅂ'), None, false),
+    ("ch", Some('ㅈ'), None, false),
+    ("f", Some('ㅍ'), Some('ㅂ'), false),
+    ("v", Some('ㅂ'), Some('ㅂ'), false),
+    ("z", Some('ㅈ'), Some('ㅈ'), false),
+];
+
+const VOWELS: [(&str, char); 21] = [
+    ("a", 'ㅏ'), ("ae", 'ㅐ'), ("ya", 'ㅑ'), ("yae", 'ㅒ'), ("eo", 'ㅓ'),
+    ("e", 'ㅔ'), ("yeo", 'ㅕ'), ("ye", 'ㅖ'), ("o", 'ㅗ'), ("wa", 'ㅘ'),
+    ("wae", 'ㅙ'), ("oe", 'ㅚ'), ("yo", 'ㅛ'), ("u", 'ㅜ'), ("wo", 'ㅝ'),
+    ("we", 'ㅞ'), ("wi", 'ㅟ'), ("yu", 'ㅠ'), ("eu", 'ㅡ'), ("ui", 'ㅢ'), ("i", 'ㅣ'),
+];
+
+const MAX_CANDIDATES: usize = 64;
+
+#[derive(Clone, Copy)]
+enum Token {
+    // (as a cho-sung, as a jong-sung)
+    Consonant(Option<u16>, Option<u16>),
+    Vowel(u16),
+
+    // a consonant that is not in the Revised Romanization
+    NonStandard(u16, Option<u16>),
+
+    // `-` or `'`
+    Separator,
+}
+
+#[derive(Clone, Copy)]
+enum ParseState {
+    None,
+    Cho(u16),
+
+    // a syllable without an initial has ㅇ as its cho-sung
+    Joong(u16, u16),
+
+    // the last consonant can either be a jong-sung, or the cho-sung of the next syllable
+    Jong(u16, u16, Token),
+}
+
+// the candidates are sorted by the score, the smaller the better
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct Score {
+    syllables: usize,
+
+    // 항을 (hang-eul) is less likely than 한글 (han-geul)
+    silent_initials_after_jong: usize,
+    tokens: usize,
+    non_standard_readings: usize,
+}
+
+#[derive(Clone)]
+struct Candidate {
+    state: ParseState,
+    result: Vec<u16>,
+    score: Score,
+}
+
+/// annyeonghaseyo -> 안녕하세요\
+/// It returns the best candidate of `romaja_candidates`.
+pub fn romaja_to_kor(string: &[u16]) -> Vec<u16> {
+    romaja_candidates(string, 1).swap_remove(0)
+}
+
+/// hangeul -> [한글, 항을]\
+/// Latin letters are read as the Revised Romanization, and the other characters are left as they are.
+/// A word that cannot be read as Hangul is also left as it is. `-` and `'` force a syllable boundary (jung-ang -> 중앙).
+///
+/// When a word can be segmented in more than one way, the candidates are ordered by these rules.
+///
+/// 1. Fewer syllables first: `ae`, `eo` and `eu` are read as one vowel (gae -> 개, not 가에).
+/// 2. A syllable without an initial after a jong-sung comes later: `ng` before a vowel is read as ㄴ and ㄱ (hangeul -> 한글, not 항을).
+/// 3. Longer spellings first: `kk`, `ss`, ... are read as tense consonants (akka -> 아까, not 악카).
+/// 4. The McCune-Reischauer readings come last: `k`, `t`, `p` and `ch` are also read as ㄱ, ㄷ, ㅂ and ㅈ (kimchi -> 킴치, 김치).
+///
+/// Alternatives differ from the best candidate in only one word. It returns at least 1 and at most `limit` candidates.
+pub fn romaja_candidates(string: &[u16], limit: usize) -> Vec<Vec<u16>> {
+    let mut words: Vec<Vec<Vec<u16>>> = vec![];
+    let mut index = 0;
+
+    while index < string.len() {
+        if is_romaja(string[index]) {
+            let start = index;
+
+            while index < string.len() && is_romaja(string[index]) {
+                index += 1;
+            }
+
+            let segmentations = segment(&string[start..index]);
+
+            if segmentations.is_empty() {
+                words.push(vec![string[start..index].to_vec()]);
+            }
+
+            else {
+                words.push(segmentations);
+            }
+        }
+
+        else {
+            words.push(vec![vec![string[index]]]);
+            index += 1;
+        }
+    }
+
+    let best: Vec<u16> = words.iter().flat_map(|w| w[0].clone()).collect();
+    let mut result = vec![best];
+
+    for (word_index, word) in words.iter().enumerate() {
+        for alternative in word.iter().skip(1) {
+            if result.len() >= limit {
+                return result;
+            }
+
+            let mut candidate = vec![];
+
+            for (i, w) in words.iter().enumerate() {
+                candidate.extend_from_slice(if i == word_index { alternative } else { &w[0] });
+            }
+
+            result.push(candidate);
+        }
+    }
+
+    result
+}
+
+fn is_romaja(c: u16) -> bool {
+    c < 128 && ((c as u8).is_ascii_alphabetic() || c == '-' as u16 || c == '\'' as u16)
+}
+
+// all the valid segmentations, sorted by the score
+fn segment(word: &[u16]) -> Vec<Vec<u16>> {
+    let word: Vec<u8> = word.iter().map(|c| (*c as u8).to_ascii_lowercase()).collect();
+
+    // `candidates[i]` are the candidates that have read `word[..i]`
+    let mut candidates: Vec<Vec<Candidate>> = vec![vec![]; word.len() + 1];
+    candidates[0].push(Candidate {
+        state: ParseState::None,
+        result: vec![],
+        score: Score { syllables: 0, silent_initials_after_jong: 0, tokens: 0, non_standard_readings: 0 },
+    });
+
+    for index in 0..word.len() {
+        let mut curr = std::mem::take(&mut candidates[index]);
+
+        // it keeps the search from exploding on long words
+        curr.sort_by_key(|c| c.score);
+        curr.truncate(MAX_CANDIDATES);
+
+        for candidate in curr.into_iter() {
+            for (length, token) in tokens_at(&word[index..]) {
+                let next = step(candidate.clone(), token).into_iter().chain(silent_initial(&candidate, token));
+                candidates[index + length].extend(next);
+            }
+        }
+    }
+
+    let mut finished: Vec<Candidate> = candidates.pop().unwrap().into_iter().filter_map(finish).collect();
+    finished.sort_by_key(|c| c.score);
+
+    let mut result: Vec<Vec<u16>> = vec![];
+
+    for c in finished.into_iter() {
+        if !result.contains(&c.result) {
+            result.push(c.result);
+        }
+    }
+
+    result
+}
+
+fn tokens_at(s: &[u8]) -> Vec<(usize, Token)> {
+    let mut result = vec![];
+
+    if s[0] == b'-' || s[0] == b'\'' {
+        return vec![(1, Token::Separator)];
+    }
+
+    for (spelling, cho, jong, standard) in CONSONANTS.iter() {
+        if s.starts_with(spelling.as_bytes()) {
+            let (cho, jong) = (cho.map(|c| c as u16), jong.map(|c| c as u16));
+
+            result.push((spelling.len(), if *standard {
+                Token::Consonant(cho, jong)
+            } else {
+                Token::NonStandard(cho.unwrap(), jong)
+            }));
+        }
+    }
+
+    for (spelling, vowel) in VOWELS.iter() {
+        if s.starts_with(spelling.as_bytes()) {
+            result.push((spelling.len(), Token::Vowel(*vowel as u16)));
+        }
+    }
+
+    result
+}
+
+fn step(mut candidate: Candidate, token: Token) -> Option<Candidate> {
+    candidate.score.tokens += 1;
+
+    let token = match token {
+        Token::NonStandard(cho, jong) => {
+            candidate.score.non_standard_readings += 1;
+            Token::Consonant(Some(cho), jong)
+        }
+        _ => token,
+    };
+
+    let next_state = match (candidate.state, token) {
+        (ParseState::None, Token::Consonant(cho, _)) => ParseState::Cho(cho?),
+        (ParseState::None, Token::Vowel(v)) => ParseState::Joong('ㅇ' as u16, v),
+        (ParseState::None, Token::Separator) => ParseState::None,
+
+        (ParseState::Cho(c), Token::Vowel(v)) => ParseState::Joong(c, v),
+
+        // a consonant cannot follow another cho-sung
+        (ParseState::Cho(_), _) => {
+            return None;
+        }
+
+        // ㄸ, ㅃ and ㅉ cannot be jong-sungs
+        (ParseState::Joong(c, j), Token::Consonant(cho, None)) => {
+            push_syllable(&mut candidate, c, j, None);
+            ParseState::Cho(cho?)
+        }
+        (ParseState::Joong(c, j), Token::Consonant(_, Some(_))) => ParseState::Jong(c, j, token),
+        (ParseState::Joong(c, j), Token::Vowel(v)) => {
+            push_syllable(&mut candidate, c, j, None);
+            ParseState::Joong('ㅇ' as u16, v)
+        }
+        (ParseState::Joong(c, j), Token::Separator) => {
+            push_syllable(&mut candidate, c, j, None);
+            ParseState::None
+        }
+
+        (ParseState::Jong(c, j, Token::Consonant(next_cho, jong)), Token::Vowel(v)) => match next_cho {
+            Some(next_cho) => {
+                push_syllable(&mut candidate, c, j, None);
+                ParseState::Joong(next_cho, v)
+            }
+            None => {
+                push_syllable(&mut candidate, c, j, jong);
+                candidate.score.silent_initials_after_jong += 1;
+                ParseState::Joong('ㅇ' as u16, v)
+            }
+        },
+        (ParseState::Jong(c, j, Token::Consonant(_, jong)), Token::Consonant(next_cho, _)) => {
+            push_syllable(&mut candidate, c, j, jong);
+            ParseState::Cho(next_cho?)
+        }
+        (ParseState::Jong(c, j, Token::Consonant(_, jong)), Token::Separator) => {
+            push_syllable(&mut candidate, c, j, jong);
+            ParseState::None
+        }
+        (ParseState::Jong(_, _, _), _) | (_, Token::NonStandard(_, _)) => unreachable!(),
+    };
+
+    candidate.state = next_state;
+    Some(candidate)
+}
+
+// the consonant before a vowel can also stay as a jong-sung, before ㅇ (hangugeo -> 한구거, 한국어)
+fn silent_initial(candidate: &Candidate, token: Token) -> Option<Candidate> {
+    match (candidate.state, token) {
+        (ParseState::Jong(c, j, Token::Consonant(Some(_), Some(jong))), Token::Vowel(v)) => {
+            let mut candidate = candidate.clone();
+
+            push_syllable(&mut candidate, c, j, Some(jong));
+            candidate.score.tokens += 1;
+            candidate.score.silent_initials_after_jong += 1;
+            candidate.state = ParseState::Joong('ㅇ' as u16, v);
+
+            Some(candidate)
+        }
+        _ => None,
+    }
+}
+
+fn finish(mut candidate: Candidate) -> Option<Candidate> {
+    match candidate.state {
+        ParseState::None => {}
+        ParseState::Cho(_) => {
+            return None;
+        }
+        ParseState::Joong(c, j) => {
+            push_syllable(&mut candidate, c, j, None);
+        }
+        ParseState::Jong(c, j, Token::Consonant(_, jong)) => {
+            push_syllable(&mut candidate, c, j, jong);
+        }
+        ParseState::Jong(_, _, _) => unreachable!(),
+    }
+
+    Some(candidate)
+}
+
+fn push_syllable(candidate: &mut Candidate, cho: u16, joong: u16, jong: Option<u16>) {
+    candidate.result.push(KorChar::combine(Some(cho), Some(joong), jong).unwrap().to_u16());
+    candidate.score.syllables += 1;
+}
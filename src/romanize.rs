@@ -0,0 +1,349 @@
+use crate::char::KorChar;
+use crate::constants::*;
+use crate::normalize::compose;
+use crate::pronounce::apply_sound_changes;
+use crate::utils::*;
+
+mod mccune_reischauer;
+mod yale;
+
+pub use mccune_reischauer::McCuneReischauer;
+pub use yale::Yale;
+
+const INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt",
+    "r", "m", "b", "pp", "s",
+    "ss", "", "j", "jj", "ch",
+    "k", "t", "p", "h"
+];
+
+const VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo",
+    "e", "yeo", "ye", "o", "wa",
+    "wae", "oe", "yo", "u", "wo",
+    "we", "wi", "yu", "eu", "ui", "i"
+];
+
+// the 7 representative sounds of the finals
+const FINALS: [&str; 27] = [
+    "k", "k", "k", "n", "n",
+    "n", "t", "l", "k", "m",
+    "l", "l", "l", "p", "l",
+    "m", "p", "p", "t", "t",
+    "ng", "t", "t", "k", "t",
+    "t", "t"
+];
+
+// one-to-one mapping of the finals, used by the transliteration mode
+const TRANSLITERATED_FINALS: [&str; 27] = [
+    "g", "kk", "gs", "n", "nj",
+    "nh", "d", "l", "lg", "lm",
+    "lb", "ls", "lt", "lp", "lh",
+    "m", "b", "bs", "s", "ss",
+    "ng", "j", "ch", "k", "t",
+    "p", "h"
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RomanizeMode {
+    /// 신라 -> Silla, 같이 -> gachi\
+    /// It romanizes how the words are pronounced, which is the default of the Revised Romanization.
+    Pronunciation,
+
+    /// 신라 -> sinla, 같이 -> gat-i\
+    /// It romanizes the spelling, jamo by jamo. Boundaries that could be read in more than one way
+    /// are always hyphenated, so that the result can be converted back to Hangul.
+    Transliteration,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RomanizeOptions {
+    pub mode: RomanizeMode,
+
+    /// 중앙 -> jung-ang, 반구대 -> ban-gudae\
+    /// It only affects `RomanizeMode::Pronunciation`.
+    pub hyphenate: bool,
+
+    /// 신라 -> Silla\
+    /// It capitalizes the first letter of each word.
+    pub capitalize: bool,
+}
+
+impl Default for RomanizeOptions {
+    fn default() -> Self {
+        RomanizeOptions {
+            mode: RomanizeMode::Pronunciation,
+            hyphenate: false,
+            capitalize: false,
+        }
+    }
+}
+
+/// 신라 -> silla, 같이 -> gachi (국어의 로마자 표기법)\
+/// Characters that are not Hangul are left as they are.
+pub fn romanize(string: &[u16], options: &RomanizeOptions) -> Vec<u16> {
+    let result = romanize_by(
+        string,
+        |word, result| romanize_word(word, options, result),
+        romanize_single_jamo,
+    );
+
+    if options.capitalize {
+        into_v16(&capitalize_words(&result))
+    }
+
+    else {
+        into_v16(&result)
+    }
+}
+
+/// A romanization system. `RomanizationScheme` lets you choose one at runtime.
+pub trait Romanizer {
+    fn romanize(&self, string: &[u16]) -> Vec<u16>;
+}
+
+impl Romanizer for RomanizeOptions {
+    fn romanize(&self, string: &[u16]) -> Vec<u16> {
+        romanize(string, self)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RomanizationScheme {
+    /// 국어의 로마자 표기법
+    Revised(RomanizeOptions),
+    McCuneReischauer(McCuneReischauer),
+    Yale(Yale),
+}
+
+impl RomanizationScheme {
+    /// "rr", "mr" or "yale", with the default options
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "rr" | "revised" => Some(RomanizationScheme::Revised(RomanizeOptions::default())),
+            "mr" | "mccune-reischauer" => Some(RomanizationScheme::McCuneReischauer(McCuneReischauer::default())),
+            "yale" => Some(RomanizationScheme::Yale(Yale)),
+            _ => None,
+        }
+    }
+}
+
+impl Romanizer for RomanizationScheme {
+    fn romanize(&self, string: &[u16]) -> Vec<u16> {
+        match self {
+            RomanizationScheme::Revised(r) => r.romanize(string),
+            RomanizationScheme::McCuneReischauer(r) => r.romanize(string),
+            RomanizationScheme::Yale(r) => r.romanize(string),
+        }
+    }
+}
+
+// `word` is called with each run of syllables, and `single_jamo` with each compatibility jamo.
+fn romanize_by<W: FnMut(&mut [KorChar], &mut String), J: Fn(u16) -> String>(string: &[u16], mut word: W, single_jamo: J) -> String {
+    let string = compose(string);
+    let mut result = String::with_capacity(string.len() * 3);
+    let mut index = 0;
+
+    while index < string.len() {
+        if is_jamo(string[index]) {
+            let mut chars = vec![];
+
+            while index < string.len() && is_jamo(string[index]) {
+                chars.push(KorChar::from_u16(string[index]).unwrap());
+                index += 1;
+            }
+
+            word(&mut chars, &mut result);
+        }
+
+        else if is_hangul(string[index]) {
+            result.push_str(&single_jamo(KorChar::from_u16(string[index]).unwrap().to_u16()));
+            index += 1;
+        }
+
+        else {
+            result.push(char::from_u32(string[index] as u32).unwrap_or('?'));
+            index += 1;
+        }
+    }
+
+    result
+}
+
+fn romanize_word(word: &mut [KorChar], options: &RomanizeOptions, result: &mut String) {
+    let transliterate = options.mode == RomanizeMode::Transliteration;
+
+    if !transliterate {
+        apply_sound_changes(word, false);
+    }
+
+    let mut prev: Option<String> = None;
+
+    for (index, c) in word.iter().enumerate() {
+        let (cho, joong, jong) = c.disassemble();
+        let (cho, joong) = (cho.unwrap(), joong.unwrap());
+        let prev_jong = if index > 0 { word[index - 1].disassemble().2 } else { None };
+
+        let initial = if cho == 'ㄹ' as u16 && (transliterate || prev_jong == Some('ㄹ' as u16)) {
+            "l"
+        } else {
+            INITIALS[ind_cho(cho)]
+        };
+
+        let final_ = match jong {
+            Some(j) if transliterate => TRANSLITERATED_FINALS[ind_jong(j)],
+            Some(j) => FINALS[ind_jong(j)],
+            None => "",
+        };
+
+        let curr = format!("{initial}{}{final_}", VOWELS[ind_joong(joong)]);
+
+        if let Some(prev) = &prev {
+            let hyphenate = if transliterate {
+                cho == 'ㅇ' as u16 || is_ambiguous(prev, &curr, &TRANSLITERATED_SYLLABLES)
+            } else {
+                options.hyphenate && is_ambiguous(prev, &curr, &SYLLABLES)
+            };
+
+            if hyphenate {
+                result.push('-');
+            }
+        }
+
+        result.push_str(&curr);
+        prev = Some(curr);
+    }
+}
+
+// the spellings of a romanization system, used to find ambiguous syllable boundaries
+struct Syllables<'a> {
+    initials: &'a [&'a str],
+    vowels: &'a [&'a str],
+    finals: &'a [&'a str],
+
+    // if it's set, a syllable without an initial is always marked, except at the beginning
+    mark_silent_initials: bool,
+}
+
+const SYLLABLES: Syllables = Syllables {
+    initials: &INITIALS,
+    vowels: &VOWELS,
+    finals: &FINALS,
+    mark_silent_initials: false,
+};
+
+const TRANSLITERATED_SYLLABLES: Syllables = Syllables {
+    initials: &INITIALS,
+    vowels: &VOWELS,
+    finals: &TRANSLITERATED_FINALS,
+    mark_silent_initials: true,
+};
+
+// `prev` and `curr` are romanized syllables.
+// If `prev + curr` can be read as one or two syllables in more than one way, it's ambiguous.
+fn is_ambiguous(prev: &str, curr: &str, syllables: &Syllables) -> bool {
+    let joined = format!("{prev}{curr}");
+
+    count_segmentations(joined.as_bytes(), syllables, 2, true) > 1
+}
+
+// it stops counting at 2
+fn count_segmentations(s: &[u8], syllables: &Syllables, max_syllables: usize, first: bool) -> usize {
+    if s.is_empty() {
+        return 1;
+    }
+
+    if max_syllables == 0 {
+        return 0;
+    }
+
+    // ㄹㄹ is romanized to `ll` in most systems
+    let initials = syllables.initials.iter().chain(["l"].iter());
+    let finals = syllables.finals;
+    let mut count = 0;
+
+    for initial in initials {
+        if initial.is_empty() && syllables.mark_silent_initials && !first {
+            continue;
+        }
+
+        let rest = match s.strip_prefix(initial.as_bytes()) {
+            Some(r) => r,
+            None => continue,
+        };
+
+        for vowel in syllables.vowels.iter() {
+            let rest = match rest.strip_prefix(vowel.as_bytes()) {
+                Some(r) => r,
+                None => continue,
+            };
+
+            for (index, final_) in finals.iter().chain([""].iter()).enumerate() {
+                // `FINALS` has duplicates
+                if finals[..index.min(finals.len())].contains(final_) {
+                    continue;
+                }
+
+                if let Some(rest) = rest.strip_prefix(final_.as_bytes()) {
+                    count += count_segmentations(rest, syllables, max_syllables - 1, false);
+
+                    if count > 1 {
+                        return count;
+                    }
+                }
+            }
+        }
+    }
+
+    count
+}
+
+fn romanize_single_jamo(c: u16) -> String {
+    if is_valid_joong(c) {
+        VOWELS[ind_joong(c)].to_string()
+    }
+
+    else if is_valid_cho(c) && c != 'ㅇ' as u16 {
+        INITIALS[ind_cho(c)].to_string()
+    }
+
+    else if is_valid_jong(c) {
+        TRANSLITERATED_FINALS[ind_jong(c)].to_string()
+    }
+
+    // ㄸ, ㅃ, ㅉ
+    else {
+        INITIALS[ind_cho(c)].to_string()
+    }
+}
+
+fn capitalize_words(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut word_start = true;
+
+    for c in s.chars() {
+        if word_start && c.is_ascii_alphabetic() {
+            result.push(c.to_ascii_uppercase());
+        }
+
+        else {
+            result.push(c);
+        }
+
+        word_start = c.is_whitespace() || (word_start && !c.is_alphanumeric());
+    }
+
+    result
+}
+
+fn ind_cho(c: u16) -> usize {
+    REV_CHOS[c as usize - 'ㄱ' as usize] as usize
+}
+
+fn ind_joong(c: u16) -> usize {
+    REV_JOONGS[c as usize - 'ㅏ' as usize] as usize
+}
+
+fn ind_jong(c: u16) -> usize {
+    REV_JONGS[c as usize - 'ㄱ' as usize] as usize
+}
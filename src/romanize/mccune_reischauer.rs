@@ -0,0 +1,93 @@
+use super::*;
+
+const INITIALS: [&str; 19] = [
+    "k", "kk", "n", "t", "tt",
+    "r", "m", "p", "pp", "s",
+    "ss", "", "ch", "tch", "ch'",
+    "k'", "t'", "p'", "h"
+];
+
+// ㄱ, ㄷ, ㅂ, ㅈ between voiced sounds
+const VOICED_INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt",
+    "r", "m", "b", "pp", "s",
+    "ss", "", "j", "tch", "ch'",
+    "k'", "t'", "p'", "h"
+];
+
+const VOWELS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "ŏ",
+    "e", "yŏ", "ye", "o", "wa",
+    "wae", "oe", "yo", "u", "wŏ",
+    "we", "wi", "yu", "ŭ", "ŭi", "i"
+];
+
+/// 한국 -> han'guk, 독립문 -> tongnimmun\
+/// It romanizes the pronunciation, like `RomanizeMode::Pronunciation`.
+/// An apostrophe separates `n` and `g` of different syllables.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct McCuneReischauer {
+    /// 한국 -> Han'guk
+    pub capitalize: bool,
+}
+
+impl Romanizer for McCuneReischauer {
+    fn romanize(&self, string: &[u16]) -> Vec<u16> {
+        let result = romanize_by(string, romanize_word, romanize_single_jamo);
+
+        if self.capitalize {
+            into_v16(&capitalize_words(&result))
+        }
+
+        else {
+            into_v16(&result)
+        }
+    }
+}
+
+fn romanize_word(word: &mut [KorChar], result: &mut String) {
+    apply_sound_changes(word, false);
+
+    let mut prev_final = None;
+
+    for c in word.iter() {
+        let (cho, joong, jong) = c.disassemble();
+        let (cho, joong) = (cho.unwrap(), joong.unwrap());
+
+        let initial = match prev_final {
+            // the beginning of a word
+            None => INITIALS[ind_cho(cho)],
+            Some("l") if cho == 'ㄹ' as u16 => "l",
+            Some("" | "n" | "m" | "ng" | "l") => VOICED_INITIALS[ind_cho(cho)],
+            Some(_) => INITIALS[ind_cho(cho)],
+        };
+
+        if prev_final == Some("n") && initial.starts_with('g') {
+            result.push('\'');
+        }
+
+        let final_ = match jong {
+            Some(j) => FINALS[ind_jong(j)],
+            None => "",
+        };
+
+        result.push_str(initial);
+        result.push_str(VOWELS[ind_joong(joong)]);
+        result.push_str(final_);
+        prev_final = Some(final_);
+    }
+}
+
+fn romanize_single_jamo(c: u16) -> String {
+    if is_valid_joong(c) {
+        VOWELS[ind_joong(c)].to_string()
+    }
+
+    else if is_valid_cho(c) && c != 'ㅇ' as u16 {
+        INITIALS[ind_cho(c)].to_string()
+    }
+
+    else {
+        FINALS[ind_jong(c)].to_string()
+    }
+}
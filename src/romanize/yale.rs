@@ -0,0 +1,95 @@
+use super::*;
+
+const INITIALS: [&str; 19] = [
+    "k", "kk", "n", "t", "tt",
+    "l", "m", "p", "pp", "s",
+    "ss", "", "c", "cc", "ch",
+    "kh", "th", "ph", "h"
+];
+
+const VOWELS: [&str; 21] = [
+    "a", "ay", "ya", "yay", "e",
+    "ey", "ye", "yey", "o", "wa",
+    "way", "oy", "yo", "wu", "we",
+    "wey", "wi", "yu", "u", "uy", "i"
+];
+
+const FINALS: [&str; 27] = [
+    "k", "kk", "ks", "n", "nc",
+    "nh", "t", "l", "lk", "lm",
+    "lp", "ls", "lth", "lph", "lh",
+    "m", "p", "ps", "s", "ss",
+    "ng", "c", "ch", "kh", "th",
+    "ph", "h"
+];
+
+const SYLLABLES: Syllables = Syllables {
+    initials: &INITIALS,
+    vowels: &VOWELS,
+    finals: &FINALS,
+    mark_silent_initials: false,
+};
+
+/// 한국어 -> hankwuk.e, 읽다 -> ilkta\
+/// The Yale romanization is morphophonemic: it follows the spelling, not the pronunciation.
+/// ㅓ is `e`, ㅡ is `u`, and ㅜ is `wu` (`u` after ㅁ, ㅂ, ㅃ and ㅍ).
+/// A period marks a syllable without an initial, if the boundary could be read in more than one way.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Yale;
+
+impl Romanizer for Yale {
+    fn romanize(&self, string: &[u16]) -> Vec<u16> {
+        into_v16(&romanize_by(string, romanize_word, romanize_single_jamo))
+    }
+}
+
+fn romanize_word(word: &mut [KorChar], result: &mut String) {
+    let mut prev: Option<String> = None;
+
+    for c in word.iter() {
+        let (cho, joong, jong) = c.disassemble();
+        let (cho, joong) = (cho.unwrap(), joong.unwrap());
+
+        let vowel = if joong == 'ㅜ' as u16 && is_bilabial(cho) {
+            "u"
+        } else {
+            VOWELS[ind_joong(joong)]
+        };
+
+        let final_ = match jong {
+            Some(j) => FINALS[ind_jong(j)],
+            None => "",
+        };
+
+        let curr = format!("{}{vowel}{final_}", INITIALS[ind_cho(cho)]);
+
+        // a consonant between vowels is read as an initial,
+        // so only a syllable without an initial has to be marked
+        if let Some(prev) = &prev {
+            if cho == 'ㅇ' as u16 && is_ambiguous(prev, &curr, &SYLLABLES) {
+                result.push('.');
+            }
+        }
+
+        result.push_str(&curr);
+        prev = Some(curr);
+    }
+}
+
+fn is_bilabial(c: u16) -> bool {
+    c == 'ㅁ' as u16 || c == 'ㅂ' as u16 || c == 'ㅃ' as u16 || c == 'ㅍ' as u16
+}
+
+fn romanize_single_jamo(c: u16) -> String {
+    if is_valid_joong(c) {
+        VOWELS[ind_joong(c)].to_string()
+    }
+
+    else if is_valid_cho(c) && c != 'ㅇ' as u16 {
+        INITIALS[ind_cho(c)].to_string()
+    }
+
+    else {
+        FINALS[ind_jong(c)].to_string()
+    }
+}
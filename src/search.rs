@@ -0,0 +1,7 @@
+mod chosung;
+mod fuzzy;
+mod prefix;
+
+pub use chosung::{ChosungMatcher, DoubleConsonants, chosung};
+pub use fuzzy::{JamoDistance, atomic_jamo, jamo_distance};
+pub use prefix::{is_prefix_match, prefix_match};
@@ -0,0 +1,170 @@
+use crate::char::{KorChar, disassemble};
+use crate::utils::*;
+use std::ops::Range;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DoubleConsonants {
+    /// ㄲ only matches ㄲ, and ㄱ only matches ㄱ.
+    Distinct,
+
+    /// ㄱ also matches ㄲ, so that the user does not have to press shift.
+    /// ㄲ still only matches ㄲ.
+    PlainMatchesDouble,
+
+    /// ㄱ and ㄲ match each other.
+    Equivalent,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChosungMatcher {
+    pub double_consonants: DoubleConsonants,
+}
+
+impl Default for ChosungMatcher {
+    fn default() -> Self {
+        ChosungMatcher {
+            double_consonants: DoubleConsonants::PlainMatchesDouble,
+        }
+    }
+}
+
+/// 대한민국 -> ㄷㅎㅁㄱ\
+/// Characters that are not syllables are left as they are.
+pub fn chosung(string: &[u16]) -> Vec<u16> {
+    string.iter().map(|c| cho_of(*c).unwrap_or(*c)).collect()
+}
+
+impl ChosungMatcher {
+    /// "ㄷㅎ", "대한민국" -> [0..2]\
+    /// "대ㅎ", "대한민국" -> [0..2]\
+    /// "ㅁㄱ", "대한민국 민국" -> [2..4, 5..7]
+    ///
+    /// A bare consonant in `query` matches a syllable that starts with it, and a syllable matches itself.
+    /// Compound consonants are read as two initials (ㄳ -> ㄱㅅ).
+    /// Whitespace in `query` is ignored, and whitespace in `target` is skipped in the middle of a match.
+    ///
+    /// It returns the ranges of the non-overlapping matches, as indices of `target`.
+    pub fn find(&self, query: &[u16], target: &[u16]) -> Vec<Range<usize>> {
+        let query = split_query(query);
+        let mut result = vec![];
+
+        if query.is_empty() {
+            return result;
+        }
+
+        let mut start = 0;
+
+        while start < target.len() {
+            match self.match_at(&query, target, start) {
+                Some(end) => {
+                    result.push(start..end);
+                    start = end;
+                }
+                None => {
+                    start += 1;
+                }
+            }
+        }
+
+        result
+    }
+
+    pub fn is_match(&self, query: &[u16], target: &[u16]) -> bool {
+        !self.find(query, target).is_empty()
+    }
+
+    // the end of the match that starts at `target[start]`
+    fn match_at(&self, query: &[u16], target: &[u16], start: usize) -> Option<usize> {
+        if is_whitespace(target[start]) {
+            return None;
+        }
+
+        let mut index = start;
+
+        for q in query.iter() {
+            while index < target.len() && is_whitespace(target[index]) {
+                index += 1;
+            }
+
+            if index == target.len() || !self.matches(*q, target[index]) {
+                return None;
+            }
+
+            index += 1;
+        }
+
+        Some(index)
+    }
+
+    fn matches(&self, q: u16, t: u16) -> bool {
+        if q == t {
+            return true;
+        }
+
+        if !is_valid_cho(q) {
+            return false;
+        }
+
+        let t = match cho_of(t) {
+            Some(t) => t,
+            None => {
+                return false;
+            }
+        };
+
+        match self.double_consonants {
+            DoubleConsonants::Distinct => q == t,
+            DoubleConsonants::PlainMatchesDouble => q == t || Some(q) == plain(t),
+            DoubleConsonants::Equivalent => plain(q).unwrap_or(q) == plain(t).unwrap_or(t),
+        }
+    }
+}
+
+// 한 -> Some(ㅎ), ㄱ -> Some(ㄱ), ㅏ -> None
+fn cho_of(c: u16) -> Option<u16> {
+    if is_jamo(c) {
+        KorChar::from_u16(c).unwrap().disassemble().0
+    }
+
+    else if is_valid_cho(c) {
+        Some(c)
+    }
+
+    else {
+        None
+    }
+}
+
+// ㄲ -> Some(ㄱ), ㄱ -> None
+fn plain(c: u16) -> Option<u16> {
+    if is_tensory(c) {
+        disassemble(c).map(|(c1, _)| c1)
+    }
+
+    else {
+        None
+    }
+}
+
+// ㄳ -> ㄱㅅ
+fn split_query(query: &[u16]) -> Vec<u16> {
+    let mut result = Vec::with_capacity(query.len());
+
+    for c in query.iter().filter(|c| !is_whitespace(**c)) {
+        match disassemble(*c) {
+            Some((c1, c2)) if is_valid_consonant(*c) && !is_valid_cho(*c) => {
+                result.push(c1);
+                result.push(c2);
+            }
+            _ => {
+                result.push(*c);
+            }
+        }
+    }
+
+    result
+}
+
+fn is_whitespace(c: u16) -> bool {
+    char::from_u32(c as u32).map(|c| c.is_whitespace()).unwrap_or(false)
+}
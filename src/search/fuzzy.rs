@@ -0,0 +1,133 @@
+use crate::char::{KorChar, disassemble};
+use crate::normalize::compose;
+use crate::utils::*;
+
+// pairs that are easily confused, either by sound or by position on the keyboard
+const SIMILAR_JAMO: [(char, char); 10] = [
+    ('ㄱ', 'ㅋ'), ('ㄷ', 'ㅌ'), ('ㅂ', 'ㅍ'), ('ㅈ', 'ㅊ'),
+    ('ㅐ', 'ㅔ'), ('ㅒ', 'ㅖ'), ('ㅏ', 'ㅑ'), ('ㅓ', 'ㅕ'),
+    ('ㅗ', 'ㅛ'), ('ㅜ', 'ㅠ'),
+];
+
+/// The costs of the edit operations of `distance`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JamoDistance {
+    pub insertion: u32,
+    pub deletion: u32,
+    pub substitution: u32,
+
+    /// ㄱ <-> ㅋ, ㅐ <-> ㅔ, ...
+    pub similar_substitution: u32,
+}
+
+impl Default for JamoDistance {
+    fn default() -> Self {
+        JamoDistance {
+            insertion: 2,
+            deletion: 2,
+            substitution: 2,
+            similar_substitution: 1,
+        }
+    }
+}
+
+/// 강 -> ㄱㅏㅇ, 곿 -> ㄱㅗㅏㄱㅅ\
+/// Compound jamo, including the tense consonants, are split (ㄲ -> ㄱㄱ). Other characters are left as they are.
+pub fn atomic_jamo(string: &[u16]) -> Vec<u16> {
+    let mut result = Vec::with_capacity(string.len() * 3);
+
+    for c in compose(string).into_iter() {
+        if is_hangul(c) {
+            let (cho, joong, jong) = KorChar::from_u16(c).unwrap().disassemble();
+
+            for jamo in [cho, joong, jong].into_iter().flatten() {
+                match disassemble(jamo) {
+                    Some((c1, c2)) => {
+                        result.push(c1);
+                        result.push(c2);
+                    }
+                    None => {
+                        result.push(jamo);
+                    }
+                }
+            }
+        }
+
+        else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+impl JamoDistance {
+    /// 강, 간 -> 2 (ㅇ -> ㄴ)\
+    /// 개, 게 -> 1 (ㅐ -> ㅔ)\
+    /// 각, 갂 -> 2 (+ㄱ)
+    ///
+    /// The weighted Levenshtein distance between the `atomic_jamo` of `a` and `b`.
+    pub fn distance(&self, a: &[u16], b: &[u16]) -> u32 {
+        let a = atomic_jamo(a);
+        let b = atomic_jamo(b);
+
+        // `prev[j]` is the distance between `a[..i]` and `b[..j]`
+        let mut prev: Vec<u32> = (0..=b.len() as u32).map(|j| j * self.insertion).collect();
+        let mut curr = vec![0; b.len() + 1];
+
+        for i in 1..=a.len() {
+            curr[0] = i as u32 * self.deletion;
+
+            for j in 1..=b.len() {
+                let substitution = prev[j - 1] + self.substitution_cost(a[i - 1], b[j - 1]);
+                let deletion = prev[j] + self.deletion;
+                let insertion = curr[j - 1] + self.insertion;
+
+                curr[j] = substitution.min(deletion).min(insertion);
+            }
+
+            std::mem::swap(&mut prev, &mut curr);
+        }
+
+        prev[b.len()]
+    }
+
+    /// The `k` candidates that are the closest to `query`, as (index, distance).
+    /// Ties are broken by the index.
+    pub fn search<T: AsRef<[u16]>>(&self, query: &[u16], candidates: &[T], k: usize) -> Vec<(usize, u32)> {
+        let mut result: Vec<(usize, u32)> = candidates.iter()
+            .enumerate()
+            .map(|(i, c)| (i, self.distance(query, c.as_ref())))
+            .collect();
+
+        result.sort_by_key(|(i, d)| (*d, *i));
+        result.truncate(k);
+
+        result
+    }
+
+    fn substitution_cost(&self, a: u16, b: u16) -> u32 {
+        if a == b {
+            0
+        }
+
+        else if is_similar(a, b) {
+            self.similar_substitution
+        }
+
+        else {
+            self.substitution
+        }
+    }
+}
+
+/// `JamoDistance::distance` with the default costs
+pub fn jamo_distance(a: &[u16], b: &[u16]) -> u32 {
+    JamoDistance::default().distance(a, b)
+}
+
+fn is_similar(a: u16, b: u16) -> bool {
+    SIMILAR_JAMO.iter().any(|(c1, c2)| {
+        (a == *c1 as u16 && b == *c2 as u16) || (a == *c2 as u16 && b == *c1 as u16)
+    })
+}
@@ -0,0 +1,131 @@
+use crate::char::{KorChar, disassemble};
+use crate::normalize::compose;
+use crate::utils::*;
+
+/// "가바", "가방" -> true\
+/// "갑", "가방" -> true (가 + ㅂ)\
+/// "고", "과자" -> true (ㅗ -> ㅘ)
+///
+/// It's like `starts_with`, but the last character of `query` is treated as a syllable that is still being typed.
+pub fn is_prefix_match(query: &[u16], target: &[u16]) -> bool {
+    prefix_match(query, target).is_some()
+}
+
+/// The number of characters of `target` that `query` covers, or `None` if it does not match.
+///
+/// The last character of `query` matches a syllable that it can become as the user keeps typing.
+///
+/// - ㄱ -> 가, 각, ...
+/// - 고 -> 과, 괜, ... (the vowel grows)
+/// - 달 -> 닭, 달걀, ... (the jong-sung grows, or becomes the cho-sung of the next syllable)
+/// - 닭 -> 달걀 (the second half of the jong-sung becomes the cho-sung of the next syllable)
+pub fn prefix_match(query: &[u16], target: &[u16]) -> Option<usize> {
+    let query = compose(query);
+    let target = compose(target);
+
+    let (last, init) = match query.split_last() {
+        Some(l) => l,
+        None => {
+            return Some(0);
+        }
+    };
+
+    if !target.starts_with(init) || target.len() == init.len() {
+        return None;
+    }
+
+    let curr = target[init.len()];
+    let next = target.get(init.len() + 1).copied();
+
+    if *last == curr {
+        return Some(init.len() + 1);
+    }
+
+    let last = KorChar::from_u16(*last).ok()?;
+    let curr = KorChar::from_u16(curr).ok()?;
+
+    match completes(&last, &curr, next.and_then(|n| KorChar::from_u16(n).ok())) {
+        Completion::None => None,
+        Completion::Curr => Some(init.len() + 1),
+        Completion::Next => Some(init.len() + 2),
+    }
+}
+
+enum Completion {
+    None,
+
+    // `last` can become `curr`
+    Curr,
+
+    // `last` can become `curr` and the cho-sung of `next`
+    Next,
+}
+
+fn completes(last: &KorChar, curr: &KorChar, next: Option<KorChar>) -> Completion {
+    let (last_cho, last_joong, last_jong) = last.disassemble();
+    let (curr_cho, curr_joong, curr_jong) = curr.disassemble();
+    let next_cho = next.and_then(|n| if is_syllable(&n) { n.disassemble().0 } else { None });
+
+    // bare vowels do not grow into syllables
+    if last_cho.is_none() || last_cho != curr_cho {
+        return Completion::None;
+    }
+
+    let last_joong = match last_joong {
+        Some(j) => j,
+
+        // ㄱ -> 가, 각, ...
+        None => {
+            return if is_syllable(curr) { Completion::Curr } else { Completion::None };
+        }
+    };
+
+    let curr_joong = match curr_joong {
+        Some(j) => j,
+        None => {
+            return Completion::None;
+        }
+    };
+
+    let last_jong = match last_jong {
+        Some(j) => j,
+
+        // 고 -> 과, 괜, ...
+        None => {
+            return if last_joong == curr_joong || disassemble(curr_joong).map(|(j, _)| j) == Some(last_joong) {
+                Completion::Curr
+            } else {
+                Completion::None
+            };
+        }
+    };
+
+    if last_joong != curr_joong {
+        return Completion::None;
+    }
+
+    match curr_jong {
+        // 달 -> 닭
+        Some(j) if disassemble(j).map(|(j, _)| j) == Some(last_jong) && !is_tensory(j) => Completion::Curr,
+
+        // 닭 -> 달걀
+        Some(j) if disassemble(last_jong).map(|(j, _)| j) == Some(j) && !is_tensory(last_jong) => {
+            if next_cho == disassemble(last_jong).map(|(_, j)| j) {
+                Completion::Next
+            } else {
+                Completion::None
+            }
+        }
+
+        Some(_) => Completion::None,
+
+        // 갑 -> 가방
+        None if next_cho == Some(last_jong) => Completion::Next,
+        None => Completion::None,
+    }
+}
+
+// `KorChar::is_valid` panics on bare jamo
+fn is_syllable(c: &KorChar) -> bool {
+    matches!(c.disassemble(), (Some(_), Some(_), _))
+}
@@ -173,3 +173,877 @@ fn validity_checkers() {
         }
     }
 }
+
+#[test]
+fn conjoining_jamo_test() {
+    let samples = vec![
+        ("한국어", "\u{1112}\u{1161}\u{11ab}\u{1100}\u{116e}\u{11a8}\u{110b}\u{1165}"),
+        ("곽", "\u{1100}\u{116a}\u{11a8}"),
+        ("값비싼", "\u{1100}\u{1161}\u{11b9}\u{1107}\u{1175}\u{110a}\u{1161}\u{11ab}"),
+        ("ㄱ가 a", "ㄱ\u{1100}\u{1161} a"),
+        ("", ""),
+    ];
+
+    for (nfc, nfd) in samples.into_iter() {
+        let nfc = into_v16(nfc);
+        let nfd = into_v16(nfd);
+
+        assert_eq!(compose(&nfd), nfc);
+        assert_eq!(decompose(&nfc), nfd);
+        assert_eq!(compose(&decompose(&nfc)), nfc);
+        assert_eq!(kor_to_qwerty(&nfd), kor_to_qwerty(&nfc));
+    }
+
+    // an LV syllable followed by T
+    assert_eq!(compose(&into_v16("가\u{11a8}")), into_v16("각"));
+
+    // T cannot be attached to an LVT syllable, or to a bare L
+    assert_eq!(compose(&into_v16("각\u{11a8}")), into_v16("각\u{11a8}"));
+    assert_eq!(compose(&into_v16("\u{1100}\u{11a8}")), into_v16("\u{1100}\u{11a8}"));
+
+    for c in 4352..4608 {
+        match to_compatibility(c) {
+            Some(k) => {
+                assert!(is_conjoining(c));
+                assert!(is_hangul(c));
+                assert_eq!(KorChar::from_u16(c).unwrap().to_u16(), k);
+            }
+            None => {
+                assert!(!is_conjoining(c));
+                assert!(KorChar::from_u16(c).is_err());
+            }
+        }
+    }
+
+    for c in CHOS.iter() {
+        assert_eq!(to_compatibility(to_conjoining_cho(*c).unwrap()), Some(*c));
+    }
+
+    for c in JOONGS.iter() {
+        assert_eq!(to_compatibility(to_conjoining_joong(*c).unwrap()), Some(*c));
+    }
+
+    for c in JONGS.iter() {
+        assert_eq!(to_compatibility(to_conjoining_jong(*c).unwrap()), Some(*c));
+    }
+
+    let gwak = KorChar::from_u16('곽' as u16).unwrap();
+    assert_eq!(KorChar::from_conjoining(&gwak.to_conjoining()), Ok(gwak));
+    assert_eq!(KorChar::from_u16('ㄳ' as u16).unwrap().to_conjoining(), vec![0x11aa]);
+    assert!(KorChar::from_conjoining(&into_v16("\u{1100}\u{1100}")).is_err());
+}
+
+#[test]
+fn romanize_test() {
+    let pronunciation = RomanizeOptions::default();
+    let samples = vec![
+        ("", ""),
+        ("한국어", "hangugeo"),
+        ("신라", "silla"),
+        ("같이", "gachi"),
+        ("굳이", "guji"),
+        ("국물", "gungmul"),
+        ("종로", "jongno"),
+        ("좋고", "joko"),
+        ("좋아", "joa"),
+        ("백마", "baengma"),
+        ("왕십리", "wangsimni"),
+        ("별내", "byeollae"),
+        ("앉아서", "anjaseo"),
+        ("닭", "dak"),
+        ("밖에서", "bakkeseo"),
+        ("서울 2호선", "seoul 2hoseon"),
+        ("ㄱ ㅏ", "g a"),
+    ];
+
+    for (hangul, roman) in samples.into_iter() {
+        assert_eq!(from_v16(&romanize(&into_v16(hangul), &pronunciation)), roman);
+    }
+
+    let hyphenated = RomanizeOptions { hyphenate: true, ..RomanizeOptions::default() };
+
+    for (hangul, roman) in [("중앙", "jung-ang"), ("반구대", "ban-gudae"), ("세운", "se-un"), ("해운대", "hae-undae"), ("신라", "silla")] {
+        assert_eq!(from_v16(&romanize(&into_v16(hangul), &hyphenated)), roman);
+    }
+
+    let capitalized = RomanizeOptions { capitalize: true, ..RomanizeOptions::default() };
+    assert_eq!(from_v16(&romanize(&into_v16("부산 해운대"), &capitalized)), "Busan Haeundae");
+
+    let strict = RomanizeOptions { mode: RomanizeMode::Transliteration, ..RomanizeOptions::default() };
+
+    for (hangul, roman) in [("신라", "sinla"), ("같이", "gat-i"), ("없었습니다", "eobs-eoss-seubnida"), ("붓꽃", "buskkoch"), ("조랑말", "jolangmal")] {
+        assert_eq!(from_v16(&romanize(&into_v16(hangul), &strict)), roman);
+    }
+}
+
+#[test]
+fn romanization_scheme_test() {
+    let mr = McCuneReischauer { capitalize: true };
+    let samples = vec![
+        ("한국", "Han'guk"),
+        ("한국어", "Han'gugŏ"),
+        ("부산", "Pusan"),
+        ("대구", "Taegu"),
+        ("서울", "Sŏul"),
+        ("김치", "Kimch'i"),
+        ("독립문", "Tongnimmun"),
+        ("신라", "Silla"),
+    ];
+
+    for (hangul, roman) in samples.into_iter() {
+        assert_eq!(from_v16(&mr.romanize(&into_v16(hangul))), roman);
+    }
+
+    let samples = vec![
+        ("한국어", "hankwuk.e"),
+        ("읽다", "ilkta"),
+        ("그릇", "kulus"),
+        ("부모", "pumo"),
+        ("쥐", "cwi"),
+        ("값", "kaps"),
+        ("아예", "a.yey"),
+    ];
+
+    for (hangul, roman) in samples.into_iter() {
+        assert_eq!(from_v16(&Yale.romanize(&into_v16(hangul))), roman);
+    }
+
+    for (name, roman) in [("rr", "hangugeo"), ("MR", "han'gugŏ"), ("yale", "hankwuk.e")] {
+        let scheme = RomanizationScheme::from_name(name).unwrap();
+        assert_eq!(from_v16(&scheme.romanize(&into_v16("한국어"))), roman);
+    }
+
+    assert!(RomanizationScheme::from_name("hepburn").is_none());
+}
+
+#[test]
+fn romaja_test() {
+    let samples = vec![
+        ("", ""),
+        ("annyeonghaseyo", "안녕하세요"),
+        ("Annyeong haseyo!", "안녕 하세요!"),
+        ("hangeul", "한글"),
+        ("seoul", "서울"),
+        ("gae", "개"),
+        ("akka", "아까"),
+        ("jung-ang", "중앙"),
+        ("saranghae", "사랑해"),
+        ("kimchi", "킴치"),
+        ("gamsahamnida", "감사함니다"),
+        ("xyz", "xyz"),
+        ("한글 hangeul", "한글 한글"),
+    ];
+
+    for (romaja, hangul) in samples.into_iter() {
+        assert_eq!(from_v16(&romaja_to_kor(&into_v16(romaja))), hangul);
+    }
+
+    let candidates: Vec<String> = romaja_candidates(&into_v16("hangeul"), 8).iter().map(|c| from_v16(c)).collect();
+    assert_eq!(candidates[0], "한글");
+    assert!(candidates.contains(&"항을".to_string()));
+
+    let candidates: Vec<String> = romaja_candidates(&into_v16("gae"), 8).iter().map(|c| from_v16(c)).collect();
+    assert_eq!(candidates, vec!["개", "가에"]);
+
+    assert_eq!(romaja_candidates(&into_v16("gae"), 1).len(), 1);
+
+    let candidates: Vec<String> = romaja_candidates(&into_v16("kimchi"), 8).iter().map(|c| from_v16(c)).collect();
+    assert!(candidates.contains(&"김치".to_string()));
+
+    // the jong-sung before ㅇ comes after the 3-syllable readings that move it, but before the 4-syllable ones
+    let candidates: Vec<String> = romaja_candidates(&into_v16("hangugeo"), 8).iter().map(|c| from_v16(c)).collect();
+    let index = candidates.iter().position(|c| c == "한국어").unwrap();
+    assert!(candidates[..index].iter().all(|c| c.chars().count() == 3));
+
+    let candidates: Vec<String> = romaja_candidates(&into_v16("meogeoyo"), 8).iter().map(|c| from_v16(c)).collect();
+    assert_eq!(candidates[0], "머거요");
+    assert!(candidates.contains(&"먹어요".to_string()));
+}
+
+#[test]
+fn pronounce_test() {
+    let samples = vec![
+        ("", ""),
+        ("옷이", "오시"),
+        ("국물", "궁물"),
+        ("신라", "실라"),
+        ("칼날", "칼랄"),
+        ("굳이", "구지"),
+        ("같이", "가치"),
+        ("좋다", "조타"),
+        ("좋아", "조아"),
+        ("않고", "안코"),
+        ("밝히다", "발키다"),
+        ("국밥", "국빱"),
+        ("학교", "학꾜"),
+        ("값이", "갑씨"),
+        ("닭", "닥"),
+        ("부엌", "부억"),
+        ("옷", "옫"),
+        ("종로", "종노"),
+        ("백로", "뱅노"),
+        ("밖에 나가", "바께 나가"),
+        ("ㄱ국물", "ㄱ궁물"),
+    ];
+
+    for (spelling, pronunciation) in samples.into_iter() {
+        assert_eq!(from_v16(&pronounce(&into_v16(spelling))), pronunciation);
+    }
+
+    let chars: Vec<KorChar> = "읽다".chars().map(|c| KorChar::from_char(c).unwrap()).collect();
+    let pronounced: String = pronounce_chars(&chars).iter().map(|c| c.to_char()).collect();
+    assert_eq!(pronounced, "익따");
+
+    // bare jamo separate words
+    for (spelling, pronunciation) in [("가ㄱ", "가ㄱ"), ("가ㅏ", "가ㅏ"), ("옷ㅏ국물", "옫ㅏ궁물")].iter() {
+        let chars: Vec<KorChar> = spelling.chars().map(|c| KorChar::from_char(c).unwrap()).collect();
+        let pronounced: String = pronounce_chars(&chars).iter().map(|c| c.to_char()).collect();
+        assert_eq!(pronounced, *pronunciation);
+    }
+}
+
+#[test]
+fn ipa_test() {
+    let options = IpaOptions::default();
+    let samples = vec![
+        ("", ""),
+        ("한국어", "[han.ɡu.ɡʌ]"),
+        ("바보", "[pa.bo]"),
+        ("신라", "[ɕil.la]"),
+        ("사랑", "[sa.ɾaŋ]"),
+        ("학교", "[hak̚.k͈jo]"),
+        ("감자 칩", "[kam.d͡ʑa] [t͡ɕʰip̚]"),
+    ];
+
+    for (spelling, ipa) in samples.into_iter() {
+        assert_eq!(from_v16(&to_ipa(&into_v16(spelling), &options)), ipa);
+    }
+
+    let options = IpaOptions { tense_mark: TenseMark::Glottal };
+    assert_eq!(from_v16(&to_ipa(&into_v16("아빠"), &options)), "[a.ˀpa]");
+}
+
+#[test]
+fn josa_test() {
+    let samples = vec![
+        ("사과", Josa::EulReul, "를"),
+        ("책", Josa::EulReul, "을"),
+        ("서울", Josa::EuroRo, "로"),
+        ("부산", Josa::EuroRo, "으로"),
+        ("학교", Josa::EuroRo, "로"),
+        ("철수", Josa::AYa, "야"),
+        ("영민", Josa::AYa, "아"),
+        ("학생", Josa::IeoYeo, "이어"),
+        ("친구", Josa::IeoYeo, "여"),
+        ("너", Josa::GwaWa, "와"),
+        ("ㄱ", Josa::EunNeun, "은"),
+        ("3", Josa::IGa, "이"),
+        ("2", Josa::IGa, "가"),
+        ("10", Josa::IGa, "이"),
+        ("7", Josa::EuroRo, "로"),
+        ("1000000000000", Josa::IGa, "가"),
+        ("100000", Josa::IGa, "이"),
+        ("1000000000", Josa::IGa, "이"),
+        ("10000000000000", Josa::IGa, "가"),
+        ("Apple", Josa::EulReul, "을"),
+        ("Computer", Josa::EulReul, "를"),
+        ("Excel", Josa::EuroRo, "로"),
+        ("SQL", Josa::EunNeun, "은"),
+        ("API", Josa::EunNeun, "는"),
+        ("사과(apple)", Josa::EulReul, "를"),
+        ("😀", Josa::EulReul, "을(를)"),
+        ("😀", Josa::EuroRo, "(으)로"),
+    ];
+
+    for (word, particle, expected) in samples.into_iter() {
+        assert_eq!(from_v16(&josa(&into_v16(word), particle)), expected, "{word}");
+    }
+
+    assert_eq!(from_v16(&attach_josa(&into_v16("사과"), Josa::EulReul)), "사과를");
+    assert_eq!(format!("{} 갑니다", with_josa("서울", Josa::EuroRo)), "서울로 갑니다");
+}
+
+#[test]
+fn chosung_test() {
+    assert_eq!(from_v16(&chosung(&into_v16("대한민국"))), "ㄷㅎㅁㄱ");
+    assert_eq!(from_v16(&chosung(&into_v16("꿈 a1"))), "ㄲ a1");
+
+    let matcher = ChosungMatcher::default();
+    let target = into_v16("대한민국 민국");
+
+    assert_eq!(matcher.find(&into_v16("ㄷㅎㅁㄱ"), &target), vec![0..4]);
+    assert_eq!(matcher.find(&into_v16("대ㅎ"), &target), vec![0..2]);
+    assert_eq!(matcher.find(&into_v16("ㅁㄱ"), &target), vec![2..4, 5..7]);
+    assert_eq!(matcher.find(&into_v16("ㄱㅁ"), &target), vec![3..6]);
+    assert_eq!(matcher.find(&into_v16("ㄷ한"), &target), vec![0..2]);
+    assert!(!matcher.is_match(&into_v16("대ㅁ"), &target));
+    assert!(!matcher.is_match(&into_v16(""), &target));
+    assert!(matcher.is_match(&into_v16("ㄳ"), &into_v16("국수")));
+
+    let target = into_v16("꿈");
+    assert!(matcher.is_match(&into_v16("ㄱ"), &target));
+    assert!(!matcher.is_match(&into_v16("ㄲ"), &into_v16("굼")));
+
+    let matcher = ChosungMatcher { double_consonants: DoubleConsonants::Distinct };
+    assert!(!matcher.is_match(&into_v16("ㄱ"), &target));
+
+    let matcher = ChosungMatcher { double_consonants: DoubleConsonants::Equivalent };
+    assert!(matcher.is_match(&into_v16("ㄲ"), &into_v16("굼")));
+}
+
+#[test]
+fn prefix_match_test() {
+    let samples = vec![
+        ("", "가방", Some(0)),
+        ("가", "가방", Some(1)),
+        ("가바", "가방", Some(2)),
+        ("갑", "가방", Some(2)),
+        ("가방", "가방", Some(2)),
+        ("ㄱ", "가방", Some(1)),
+        ("가ㅂ", "가방", Some(2)),
+        ("고", "과자", Some(1)),
+        ("달", "닭고기", Some(1)),
+        ("닭", "달걀", Some(2)),
+        ("갂", "가까이", Some(2)),
+        ("각", "가방", None),
+        ("각", "갂", None),
+        ("갑", "가", None),
+        ("나", "가방", None),
+        ("과", "고", None),
+        ("가방", "가", None),
+        ("ab", "abc", Some(2)),
+        ("갑", "가ㄴ", None),
+        ("갑", "가ㅏ", None),
+        ("ㄱ", "ㄱㅏ", Some(1)),
+    ];
+
+    for (query, target, expected) in samples.into_iter() {
+        assert_eq!(prefix_match(&into_v16(query), &into_v16(target)), expected, "{query} {target}");
+    }
+
+    assert!(is_prefix_match(&into_v16("대한미"), &into_v16("대한민국")));
+    assert!(!is_prefix_match(&into_v16("대한무"), &into_v16("대한민국")));
+}
+
+#[test]
+fn jamo_distance_test() {
+    assert_eq!(from_v16(&atomic_jamo(&into_v16("강 곿"))), "ㄱㅏㅇ ㄱㅗㅏㄱㅅ");
+
+    let samples = vec![
+        ("강", "강", 0),
+        ("강", "간", 2),
+        ("개", "게", 1),
+        ("각", "갂", 2),
+        ("카", "가", 1),
+        ("과", "가", 2),
+        ("", "가", 4),
+        ("abc", "abd", 2),
+    ];
+
+    for (a, b, expected) in samples.into_iter() {
+        assert_eq!(jamo_distance(&into_v16(a), &into_v16(b)), expected, "{a} {b}");
+        assert_eq!(jamo_distance(&into_v16(b), &into_v16(a)), expected, "{b} {a}");
+    }
+
+    let candidates: Vec<Vec<u16>> = ["간장", "된장", "강정", "고추장"].iter().map(|c| into_v16(c)).collect();
+    let distance = JamoDistance::default();
+
+    assert_eq!(distance.search(&into_v16("강장"), &candidates, 2), vec![(0, 2), (2, 2)]);
+    assert_eq!(distance.search(&into_v16("강장"), &candidates, 10).len(), 4);
+}
+
+#[test]
+fn input_context_test() {
+    let mut context = InputContext::new();
+
+    for key in "rhkr".chars() {
+        assert!(context.process(key as u16));
+    }
+
+    assert_eq!(from_v16(&context.preedit()), "곽");
+    assert!(context.commit().is_empty());
+
+    let preedits = ["과", "고", "ㄱ", ""];
+
+    for preedit in preedits.iter() {
+        assert!(context.backspace());
+        assert_eq!(from_v16(&context.preedit()), *preedit);
+    }
+
+    assert!(!context.backspace());
+    assert!(context.is_empty());
+
+    // 닭 -> 달 -> 다
+    for key in "ekfr".chars() {
+        context.process(key as u16);
+    }
+
+    context.backspace();
+    assert_eq!(from_v16(&context.preedit()), "달");
+
+    // 달 + ㄱ + ㅏ -> 달가
+    context.process('r' as u16);
+    context.process('k' as u16);
+    assert_eq!(from_v16(&context.commit()), "달");
+    assert_eq!(from_v16(&context.preedit()), "가");
+
+    assert!(!context.process(' ' as u16));
+    assert_eq!(from_v16(&context.commit()), "가");
+    assert!(context.preedit().is_empty());
+
+    // ml -> ㅢ -> ㅡ
+    context.process('m' as u16);
+    context.process('l' as u16);
+    assert_eq!(from_v16(&context.preedit()), "ㅢ");
+    context.backspace();
+    assert_eq!(from_v16(&context.flush()), "ㅡ");
+}
+
+#[test]
+fn sebeolsik_test() {
+    let layout = Layout::sebeolsik_390();
+
+    assert_eq!(from_v16(&keys_to_kor(&into_v16("mfskgw"), &layout)), "한글");
+    assert_eq!(from_v16(&keys_to_kor(&into_v16("kkfufwx"), &layout)), "까닭");
+    assert_eq!(from_v16(&keys_to_kor(&into_v16("jfsheaM"), &layout)), "안녕1");
+    assert_eq!(from_v16(&kor_to_keys(&into_v16("닭"), &layout)), "ufD");
+    assert_eq!(from_v16(&kor_to_keys(&into_v16("곽 1"), &layout)), "kvfx M");
+
+    let sample = "안녕하세요, 까치가 닭을 읽었다. ㄱ ㅏ 123";
+
+    for name in ["dubeolsik", "390", "final", "noshift"].iter() {
+        let layout = Layout::from_name(name).unwrap();
+        let keys = kor_to_keys(&into_v16(sample), &layout);
+
+        assert_eq!(from_v16(&keys_to_kor(&keys, &layout)), sample, "{name}");
+    }
+
+    assert_eq!(KorChar::from_qwerty_with_layout(&into_v16("kvfx"), &layout).unwrap().to_char(), '곽');
+    assert_eq!(from_v16(&KorChar::from_char('곽').unwrap().to_qwerty_with_layout(&layout)), "kvfx");
+
+    let mut context = InputContext::with_layout(layout);
+
+    for key in "kvfx".chars() {
+        context.process(key as u16);
+    }
+
+    assert_eq!(from_v16(&context.preedit()), "곽");
+
+    let preedits = ["과", "고", "ㄱ", ""];
+
+    for preedit in preedits.iter() {
+        assert!(context.backspace());
+        assert_eq!(from_v16(&context.preedit()), *preedit);
+    }
+
+    assert!(!context.backspace());
+
+    // the double tap is undone key by key: 까 -> ㄲ -> ㄱ
+    for key in "kkf".chars() {
+        context.process(key as u16);
+    }
+
+    assert_eq!(from_v16(&context.preedit()), "까");
+
+    let preedits = ["ㄲ", "ㄱ", ""];
+
+    for preedit in preedits.iter() {
+        assert!(context.backspace());
+        assert_eq!(from_v16(&context.preedit()), *preedit);
+    }
+
+    // a custom Dubeolsik layout with double-tap tense consonants
+    let layout = Layout::parse("kind dubeolsik\ndouble-tap-tense yes\nr jamo ㄱ\nk jamo ㅏ").unwrap();
+    let mut context = InputContext::with_layout(layout);
+
+    for key in "rr".chars() {
+        context.process(key as u16);
+    }
+
+    assert_eq!(from_v16(&context.preedit()), "ㄲ");
+    assert!(context.backspace());
+    assert_eq!(from_v16(&context.preedit()), "ㄱ");
+}
+
+#[test]
+fn moachigi_test() {
+    let type_keys = |keys: &str, moachigi: bool| {
+        let mut context = InputContext::with_layout(Layout::sebeolsik_390());
+        context.set_moachigi(moachigi);
+
+        for key in keys.chars() {
+            context.process(key as u16);
+        }
+
+        from_v16(&context.flush())
+    };
+
+    // ㅏ ㄱ ㄴ, ㄴ ㄱ ㅏ, ㄱ ㄴ ㅏ
+    for keys in ["fks", "skf", "ksf"].iter() {
+        assert_eq!(type_keys(keys, true), "간");
+    }
+
+    assert_eq!(type_keys("fks", false), "ㅏㄱㄴ");
+
+    // ㄱ ㄱ ㅏ, ㄱ ㅏ ㄱ ㅏ
+    assert_eq!(type_keys("kkf", true), "까");
+    assert_eq!(type_keys("kfkf", true), "가가");
+
+    // ㅗ ㄱ ㅏ: ㅏ does not combine with ㅗ, since ㄱ came in between
+    assert_eq!(type_keys("vkf", true), "고ㅏ");
+    assert_eq!(type_keys("kvf", true), "과");
+}
+
+#[test]
+fn layout_file_test() {
+    let definition = "
+// a Dubeolsik layout that types tense consonants by double tapping
+name test
+kind dubeolsik
+double-tap-tense yes
+combine ㄱ ㄱ ㄲ
+combine ㅗ ㅏ ㅘ
+
+r jamo ㄱ | char R
+h jamo ㅗ
+k jamo ㅏ
+";
+    let layout = Layout::parse(definition).unwrap();
+
+    assert_eq!(layout.name, "test");
+    assert_eq!(from_v16(&keys_to_kor(&into_v16("rrhkr R"), &layout)), "꽉 R");
+
+    // ㄱㅅ -> ㄳ is not in the `combine` lines
+    assert_eq!(layout.combine('ㄱ' as u16, 'ㅅ' as u16), None);
+    assert_eq!(layout.split('ㄳ' as u16), None);
+    assert_eq!(layout.split('ㅘ' as u16), Some(('ㅗ' as u16, 'ㅏ' as u16)));
+    assert_eq!(from_v16(&InputContext::with_layout_ref(&layout).type_keys(&into_v16("rhk"))), "과");
+    assert_eq!(from_v16(&kor_to_keys(&into_v16("꽉"), &layout)), "rrhkr");
+
+    assert_eq!(Layout::parse("kind dubeolsik\nq jamo ㅂ\nQ jamo ㅃ | jamo ㅃ"), Err(KorError::InvalidLayout(3, "Q does not have a shifted key".to_string())));
+    assert_eq!(Layout::parse("kind sebeolsik\n\nq cho ㄳ"), Err(KorError::InvalidLayout(3, "ㄳ cannot be a cho".to_string())));
+    assert!(Layout::parse("q jamo ㅂ").is_err());
+
+    // a combination that changes the role of the jamo would break the automaton
+    for combination in ["combine ㅗ ㅏ ㄱ", "combine ㄱ ㄱ ㅏ", "combine ㄱ ㅅ ㅏ", "combine ㄱ ㅏ ㄱ"].iter() {
+        assert_eq!(
+            Layout::parse(&format!("kind dubeolsik\n{combination}")),
+            Err(KorError::InvalidLayout(2, "a combination has to be three vowels or three consonants: `combine ㄱ ㅅ ㄳ`".to_string())),
+        );
+    }
+
+    assert_eq!(Layout::dubeolsik(), Layout::from_name("2").unwrap());
+    assert!(!Layout::dubeolsik().double_tap_tense);
+    assert_eq!(from_v16(&qwerty_to_kor(&into_v16("rrk"))), "ㄱ가");
+}
+
+#[test]
+fn cheonjiin_test() {
+    assert_eq!(from_v16(&cheonjiin_to_kor(&into_v16("81255120"))), "사랑");
+    assert_eq!(from_v16(&cheonjiin_to_kor(&into_v16("4124*412"))), "각가");
+    assert_eq!(from_v16(&cheonjiin_to_kor(&into_v16("41244412"))), "가까");
+    assert_eq!(from_v16(&cheonjiin_to_kor(&into_v16("02312#41255"))), "와 갈");
+
+    let mut context = KeypadContext::new();
+
+    // 각 + ㆍ -> 각ㆍ, + ㅣ -> 가거
+    for key in "41242".chars() {
+        context.process(key as u16);
+    }
+
+    assert_eq!(from_v16(&context.preedit()), "각ㆍ");
+    context.process('2' as u16);
+    assert_eq!(from_v16(&context.preedit()), "각ᆢ");
+    context.process('1' as u16);
+    assert_eq!(from_v16(&context.preedit()), "가겨");
+
+    let preedits = ["각ᆢ", "각ㆍ", "각", "가", "기", "ㄱ", ""];
+
+    for preedit in preedits.iter() {
+        assert!(context.backspace());
+        assert_eq!(from_v16(&context.preedit()), *preedit);
+    }
+
+    assert!(!context.backspace());
+
+    let sample = "안녕하세요 까치가 닭을 읽었다 ㄱ ㅏ 뷁 쌰 ㆍ";
+    let keys = kor_to_cheonjiin(&into_v16(sample));
+
+    assert_eq!(from_v16(&cheonjiin_to_kor(&keys)), sample);
+}
+
+#[test]
+fn keypad_test() {
+    let naratgeul = KeypadLayout::Naratgeul;
+    let sky = KeypadLayout::Sky;
+
+    // ㄱ + 획추가 -> ㅋ, ㅏ -> ㅓ, ㄴ + 획추가 * 2 -> ㅌ, ㄱ + 쌍자음 -> ㄲ
+    assert_eq!(from_v16(&keypad_to_kor(&into_v16("1*33"), naratgeul)), "커");
+    assert_eq!(from_v16(&keypad_to_kor(&into_v16("2**9 1#3"), naratgeul)), "티 까");
+    assert_eq!(from_v16(&keypad_to_kor(&into_v16("63 6633"), naratgeul)), "ㅘ ㅝ");
+    assert_eq!(from_v16(&kor_to_keypad(&into_v16("카넛"), naratgeul)), "1*32337");
+
+    // ㅎ: 0 twice, ㅏ -> ㅑ, ㄴ -> ㄹ
+    assert_eq!(from_v16(&keypad_to_kor(&into_v16("0033-355"), sky)), "햐ㅏㄹ");
+    assert_eq!(from_v16(&kor_to_keypad(&into_v16("까치"), sky)), "1113**2");
+
+    let mut context = KeypadContext::with_layout(sky);
+    assert_eq!(from_v16(&context.type_keys(&into_v16("9#"))), "ㅗㅜ");
+
+    let sample = "안녕하세요 까치가 닭을 읽었다 ㄱ ㅏ 뷁 쌰 왜 의사";
+
+    for layout in [KeypadLayout::Cheonjiin, naratgeul, sky].into_iter() {
+        let keys = kor_to_keypad(&into_v16(sample), layout);
+
+        assert_eq!(from_v16(&keypad_to_kor(&keys, layout)), sample, "{layout:?}");
+    }
+}
+
+#[test]
+fn physical_layout_test() {
+    assert_eq!(from_v16(&qwerty_to_kor(&PhysicalLayout::Dvorak.to_qwerty(&into_v16("itopmu")))), "한글");
+    assert_eq!(from_v16(&PhysicalLayout::Dvorak.from_qwerty(&kor_to_qwerty(&into_v16("한글")))), "itopmu");
+    assert_eq!(from_v16(&PhysicalLayout::Azerty.to_qwerty(&into_v16("qmù1"))), "a;'!");
+    assert_eq!(from_v16(&PhysicalLayout::Qwertz.from_qwerty(&into_v16("Hey, y?"))), "Hez, z_");
+
+    let keys = "`1234567890-=qwertyuiop[]\\asdfghjkl;'zxcvbnm,./~!@#$%^&*()_+QWERTYUIOP{}|ASDFGHJKL:\"ZXCVBNM<>? 한";
+
+    for name in ["qwerty", "dvorak", "colemak", "azerty", "qwertz"].iter() {
+        let physical = PhysicalLayout::from_name(name).unwrap();
+
+        assert_eq!(from_v16(&physical.to_qwerty(&physical.from_qwerty(&into_v16(keys)))), keys, "{name}");
+    }
+}
+
+#[test]
+fn key_event_test() {
+    let type_events = |events: &[(u16, bool, bool)], caps_lock: CapsLock| {
+        let mut context = InputContext::new();
+        context.set_caps_lock(caps_lock);
+
+        let mut result = vec![];
+
+        for (code, shift, caps) in events.iter() {
+            if !context.process_key_event(KeyEvent::new(*code, *shift, *caps)) {
+                result.extend(context.commit());
+            }
+        }
+
+        result.extend(context.flush());
+        from_v16(&result)
+    };
+
+    // r, k, Backspace, h, k, r -> 곽, Shift + r -> ㄲ
+    let events = [(19, false, false), (37, false, false), (14, false, false), (35, false, false), (37, false, false), (19, false, false)];
+    assert_eq!(type_events(&events, CapsLock::Ignore), "곽");
+    assert_eq!(type_events(&[(19, true, false), (37, false, false)], CapsLock::Ignore), "까");
+
+    // Caps Lock + r, Caps Lock + Shift + r, Caps Lock + o
+    let events = [(19, false, true), (37, false, true), (19, true, true), (24, false, true)];
+    assert_eq!(type_events(&events, CapsLock::Ignore), "가깨");
+    assert_eq!(type_events(&events, CapsLock::Invert), "까걔");
+
+    assert_eq!(KeyEvent::new(2, true, true).to_char(CapsLock::Invert), Some('!' as u16));
+    assert_eq!(KeyEvent::new(28, false, false).to_char(CapsLock::Ignore), None);
+}
+
+#[test]
+fn capi_test() {
+    use crate::capi::*;
+    use std::ffi::CString;
+
+    // null-terminated UCS-4 -> String
+    let read = |s: *const u32| unsafe {
+        let mut result = String::new();
+        let mut index = 0;
+
+        while *s.add(index) != 0 {
+            result.push(char::from_u32(*s.add(index)).unwrap());
+            index += 1;
+        }
+
+        result
+    };
+
+    let keyboard = CString::new("2").unwrap();
+
+    unsafe {
+        let hic = hangul_ic_new(keyboard.as_ptr());
+        assert!(hangul_ic_is_empty(hic));
+
+        for key in "gks".chars() {
+            assert!(hangul_ic_process(hic, key as i32));
+        }
+
+        assert_eq!(read(hangul_ic_get_preedit_string(hic)), "한");
+        assert!(hangul_ic_process(hic, 'r' as i32));
+        assert_eq!(read(hangul_ic_get_commit_string(hic)), "한");
+        assert_eq!(read(hangul_ic_get_preedit_string(hic)), "ㄱ");
+
+        // the caller types the space
+        assert!(!hangul_ic_process(hic, ' ' as i32));
+        assert_eq!(read(hangul_ic_get_commit_string(hic)), "ㄱ");
+        assert_eq!(read(hangul_ic_get_preedit_string(hic)), "");
+
+        let keyboard = CString::new("39").unwrap();
+        hangul_ic_select_keyboard(hic, keyboard.as_ptr());
+
+        for key in "kf".chars() {
+            hangul_ic_process(hic, key as i32);
+        }
+
+        assert!(hangul_ic_backspace(hic));
+        assert_eq!(read(hangul_ic_get_preedit_string(hic)), "ㄱ");
+        assert_eq!(read(hangul_ic_flush(hic)), "ㄱ");
+        assert!(hangul_ic_is_empty(hic));
+
+        hangul_ic_delete(hic);
+
+        let keyboard = CString::new("unknown").unwrap();
+        assert!(hangul_ic_new(keyboard.as_ptr()).is_null());
+    }
+}
+
+#[test]
+fn offset_test() {
+    let (kor, offsets) = qwerty_to_kor_with_offsets(&into_v16("dkssud, rtk"));
+    assert_eq!(from_v16(&kor), "안녕, ㄱ사");
+    assert_eq!(offsets, vec![0..3, 3..6, 6..7, 7..8, 8..9, 9..11]);
+
+    // ㅘ and ㄺ are typed with 2 keys, and the jong-sung ㄺ is split (달기)
+    let (kor, offsets) = qwerty_to_kor_with_offsets(&into_v16("rhkRkekfr ekfrl"));
+    assert_eq!(from_v16(&kor), "과까닭 달기");
+    assert_eq!(offsets, vec![0..3, 3..5, 5..9, 9..10, 10..13, 13..15]);
+
+    // ㄳ is typed with a single key
+    let (kor, offsets) = qwerty_to_kor_with_offsets(&into_v16("ㄳk"));
+    assert_eq!(from_v16(&kor), "ㄱ사");
+    assert_eq!(offsets, vec![0..1, 0..2]);
+
+    // NFD
+    let (qwerty, offsets) = kor_to_qwerty_with_offsets(&into_v16("가\u{1100}\u{1161}\u{11A8} a"));
+    assert_eq!(from_v16(&qwerty), "rkrkr a");
+    assert_eq!(offsets, vec![0..1, 0..1, 1..4, 1..4, 1..4, 4..5, 5..6]);
+}
+
+#[test]
+fn mistyped_test() {
+    let detect = |s: &str| detect_mistyped(&into_v16(s));
+
+    assert_eq!(detect("dkssudgktpdy"), Some(Mistyped::KoreanInLatinMode));
+    assert_eq!(detect("gks"), Some(Mistyped::KoreanInLatinMode));
+    assert_eq!(detect("rkskek"), Some(Mistyped::KoreanInLatinMode));
+    assert_eq!(detect("tkfkdgo"), Some(Mistyped::KoreanInLatinMode));
+    assert_eq!(detect("ㅗ디ㅣㅐ"), Some(Mistyped::LatinInKoreanMode));
+
+    for word in ["hello", "an", "the", "Seoul", "C++", "2023", "안녕", "ㅋㅋㅋ", "ㅠㅠ"].iter() {
+        assert_eq!(detect(word), None, "{word}");
+    }
+
+    assert_eq!(mistype_score(&into_v16("ㅗ디ㅣㅐ"), Mistyped::LatinInKoreanMode), 0.75);
+
+    assert_eq!(
+        from_v16(&fix_mistyped(&into_v16("dkssudgktpdy, ㅗ디ㅣㅐ there!  세상 ㅋㅋ"))),
+        "안녕하세요, hello there!  세상 ㅋㅋ"
+    );
+
+    // English that happens to form Hangul
+    let words = [
+        "world", "word", "work", "would", "could", "good", "food", "you", "your", "have", "from", "with",
+        "people", "think", "love", "game", "google", "rust", "code", "help", "yes", "no", "gg", "zzz", "xx",
+    ];
+
+    for word in words.iter() {
+        assert_eq!(detect(word), None, "{word}");
+    }
+
+    for text in ["hello world, this is a test", "gg ss mm xx zzz", "I love you so much", "Nice to meet you!"].iter() {
+        assert_eq!(from_v16(&fix_mistyped(&into_v16(text))), *text);
+    }
+}
+
+#[test]
+fn structured_test() {
+    let fix = |s: &str| from_v16(&fix_structured(&into_v16(s)));
+
+    assert_eq!(fix("ㅈㅈㅈ.ㅎㅐㅐ히ㄷ.채ㅡ"), "www.google.com");
+    assert_eq!(fix("ㅣㄴ -ㅣㅁ"), "ls -la");
+    assert_eq!(fix("메일은 ㅁ으ㅑㅜ@ㅎㅡ먀ㅣ.채ㅡ 으로 보내 주세요."), "메일은 admin@gmail.com 으로 보내 주세요.");
+    assert_eq!(fix("ㅊㅇ ~/ㅇㅐ쳐ㅡ둣ㄴ\n안녕하세요. 끝."), "cd ~/documents\n안녕하세요. 끝.");
+    assert_eq!(fix("ㅎㅑㅅ ㄴㅅㅁ션"), "ㅎㅑㅅ ㄴㅅㅁ션");
+
+    for text in ["예/아니오", "남/여", "서울.부산", "한글.txt", "회의는 3:30에 시작", "v1.2를 받아"].iter() {
+        assert_eq!(fix(text), *text);
+    }
+
+    assert_eq!(fix("ㅊㅁㅅ.ㅔㅜㅎ 3:30에"), "cat.png 3:30에");
+
+    assert_eq!(from_v16(&fix_command(&into_v16("ㅎㅑㅅ ㄴㅅㅁ션"))), "git status");
+    assert_eq!(from_v16(&fix_command(&into_v16("ㅎㅑㅅ 채ㅡㅡㅑㅅ -ㅡ 한글"))), "git commit -m 한글");
+}
+
+#[test]
+fn lossless_test() {
+    let lossless = qwerty_to_kor_lossless(&into_v16("dKssud"));
+
+    assert_eq!(from_v16(&lossless.text), "안녕");
+    assert_eq!(lossless.patches, vec![(0..1, into_v16("dKs"))]);
+    assert!(qwerty_to_kor_lossless(&into_v16("dkssud")).patches.is_empty());
+
+    let samples = [
+        "", "K", "rK", "rt", "rtk", "ㄳk", "rkrk", "GKS", "DKSSUD", "Qwerty!@", "hk", "rhkRkekfr ekfrl", "dkssud, 세상!", "qkqk  ",
+    ];
+
+    for sample in samples.iter() {
+        let lossless = qwerty_to_kor_lossless(&into_v16(sample));
+
+        assert_eq!(from_v16(&lossless.text), from_v16(&qwerty_to_kor(&into_v16(sample))));
+        assert_eq!(from_v16(&lossless_to_qwerty(&lossless)), *sample);
+    }
+
+    let layout = Layout::sebeolsik_390();
+    let lossless = keys_to_kor_lossless(&into_v16("kfdKfd"), &layout);
+
+    assert_eq!(from_v16(&lossless_to_keys(&lossless, &layout)), "kfdKfd");
+}
+
+#[test]
+fn password_test() {
+    let candidates: Vec<String> = password_candidates(&into_v16("rkskek")).iter().map(|c| from_v16(c)).collect();
+
+    assert_eq!(candidates[0], "rkskek");
+
+    for candidate in ["가나다", "ㄱㅏㄴㅏㄷㅏ", "가나", "RKSKEK", "까나따"].iter() {
+        assert!(candidates.contains(&candidate.to_string()), "{candidate}");
+    }
+
+    let candidates: Vec<String> = password_candidates(&into_v16("ㅔㅁㄴㄴ재ㄱㅇ1!")).iter().map(|c| from_v16(c)).collect();
+
+    assert!(candidates.contains(&String::from("password1!")));
+    assert!(candidates.contains(&String::from("PASSWORD1!")));
+
+    for (index, candidate) in candidates.iter().enumerate() {
+        assert!(!candidates[index + 1..].contains(candidate));
+    }
+
+    assert_eq!(from_v16(&invert_case(&into_v16("Hello, 세상"))), "hELLO, 세상");
+}
+
+#[test]
+fn caps_lock_test() {
+    for (keys, caps_lock) in [("DKSSUD", true), ("QKQ", true), ("RKSKEK rk", true), ("dkssud", false), ("Qkq", false), ("Q", false), ("123", false)].iter() {
+        assert_eq!(is_caps_lock_on(&into_v16(keys)), *caps_lock, "{keys}");
+    }
+
+    // acronyms and mixed English
+    for text in ["OK", "USA 좋아", "Hello WORLD", "NASA", "ASAP", "I LOVE YOU", "KOREA", "HTTP/2", "Rust is FUN"].iter() {
+        assert!(!is_caps_lock_on(&into_v16(text)), "{text}");
+    }
+
+    let convert = |s: &str, c: CapsLock| from_v16(&qwerty_to_kor_with_caps_lock(&into_v16(s), c));
+
+    assert_eq!(convert("QKQ", CapsLock::Ignore), "빠ㅃ");
+    assert_eq!(convert("QKQ", CapsLock::Invert), "밥");
+    assert_eq!(convert("DKSSUD", CapsLock::Invert), "안녕");
+    assert_eq!(convert("TPDY", CapsLock::Invert), "세요");
+}
@@ -1,14 +1,3 @@
-#[inline]
-pub fn to_lower(c: &u16) -> u16 {
-    if 64 < *c && *c < 91 {
-        *c + 32
-    }
-
-    else {
-        *c
-    }
-}
-
 /// ㄲㄸㅃㅆㅉ
 #[inline]
 pub fn is_tensory(c: u16) -> bool {
@@ -47,7 +36,7 @@ pub fn is_valid_jong(c: u16) -> bool {
 
 #[inline]
 pub fn is_hangul(c: u16) -> bool {
-    is_jamo(c) || is_non_jamo(c)
+    is_jamo(c) || is_non_jamo(c) || is_conjoining(c)
 }
 
 #[inline]
@@ -60,6 +49,33 @@ pub fn is_non_jamo(c: u16) -> bool {
     12592 < c && c < 12644
 }
 
+/// modern conjoining jamo: ᄀ ~ ᄒ, ᅡ ~ ᅵ, ᆨ ~ ᇂ
+#[inline]
+pub fn is_conjoining(c: u16) -> bool {
+    is_conjoining_cho(c) || is_conjoining_joong(c) || is_conjoining_jong(c)
+}
+
+/// ᄀᄁᄂᄃᄄᄅᄆᄇᄈᄉᄊᄋᄌᄍᄎᄏᄐᄑᄒ\
+/// 4352~4370
+#[inline]
+pub fn is_conjoining_cho(c: u16) -> bool {
+    4351 < c && c < 4371
+}
+
+/// ᅡᅢᅣᅤᅥᅦᅧᅨᅩᅪᅫᅬᅭᅮᅯᅰᅱᅲᅳᅴᅵ\
+/// 4449~4469
+#[inline]
+pub fn is_conjoining_joong(c: u16) -> bool {
+    4448 < c && c < 4470
+}
+
+/// ᆨᆩᆪᆫᆬᆭᆮᆯᆰᆱᆲᆳᆴᆵᆶᆷᆸᆹᆺᆻᆼᆽᆾᆿᇀᇁᇂ\
+/// 4520~4546
+#[inline]
+pub fn is_conjoining_jong(c: u16) -> bool {
+    4519 < c && c < 4547
+}
+
 #[inline]
 pub fn is_valid_consonant(c: u16) -> bool {
     12592 < c && c < 12623
@@ -79,3 +95,21 @@ pub fn from_v16(v: &[u16]) -> String {
 pub fn into_v16(s: &str) -> Vec<u16> {
     s.encode_utf16().collect()
 }
+
+/// "Hello" -> "hELLO"\
+/// what Caps Lock does to the ASCII letters
+pub fn invert_case(string: &[u16]) -> Vec<u16> {
+    string.iter().map(|c| {
+        if *c < 128 && (*c as u8).is_ascii_lowercase() {
+            (*c as u8).to_ascii_uppercase() as u16
+        }
+
+        else if *c < 128 && (*c as u8).is_ascii_uppercase() {
+            (*c as u8).to_ascii_lowercase() as u16
+        }
+
+        else {
+            *c
+        }
+    }).collect()
+}